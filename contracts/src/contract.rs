@@ -3,19 +3,30 @@
 mod state;
 
 use linera_sdk::{
-    linera_base_types::WithContractAbi,
-    views::{RootView, View},
+    linera_base_types::{StreamName, WithContractAbi},
+    views::{RegisterView, RootView, View},
     Contract, ContractRuntime,
 };
 
-use contracts::{CasinoParams, CasinoInit, Operation, Message, GameType, GameAction, GameResult, Card, RouletteBet, RouletteBetType, BaccaratBetType};
+use contracts::{BlackjackRules, CasinoEvent, CasinoParams, CasinoInit, Operation, Message, GameType, GameAction, GameResult, Card, HandState, RouletteBet, BaccaratBetType, BaccaratSideBet, BaccaratSideBetResult, BaccaratWager, BaccaratWagerResult, SeatResult, SideBetResult, SideBets, SHOE_STREAM};
+use contracts::fairness::{game_seed, is_hex_32_bytes, verify_seed_commitment, ClientSeed};
+use contracts::rng::RngVersion;
+use contracts::games::baccarat::{
+    baccarat_side_bets_max_liability, baccarat_side_bets_total, baccarat_wagers_max_liability, baccarat_wagers_total,
+    run_baccarat_game, settle_baccarat_side_bets, settle_baccarat_wagers, validate_baccarat_side_bets,
+    validate_baccarat_wagers,
+};
+use contracts::games::blackjack::{
+    self, apply_blackjack_action, available_actions, blackjack_max_liability, insurance_stake, next_open_hand, seat_hands, seat_results,
+    settle_side_bets, side_bets_max_liability, validate_seat_bets, BlackjackTable, ALLOWED_BETS, SIDE_BET_MIN_DECKS,
+};
+use contracts::games::roulette::{self, calculate_roulette_payout, roulette_max_liability, spin_roulette};
+use contracts::games::shoe::{Shoe, ShoeTable};
 
-use self::state::{ContractsState, PendingGame, ActiveGame, GamePhase, GameRecord, PendingRouletteGame, ALLOWED_BETS};
+use self::state::{ContractsState, FaucetClaim, PendingGame, ActiveGame, GamePhase, GameRecord};
 
-const SUITS: [&str; 4] = ["clubs", "diamonds", "hearts", "spades"];
-const VALUES: [&str; 13] = [
-    "2", "3", "4", "5", "6", "7", "8", "9", "10", "jack", "queen", "king", "ace",
-];
+/// History `game_id` for requests the Bank bounced before assigning an ID
+const UNASSIGNED_GAME_ID: u64 = u64::MAX;
 
 pub struct ContractsContract {
     state: ContractsState,
@@ -32,7 +43,7 @@ impl Contract for ContractsContract {
     type Message = Message;
     type Parameters = CasinoParams;  // Application Parameters with bank_chain_id
     type InstantiationArgument = CasinoInit;
-    type EventValue = ();
+    type EventValue = CasinoEvent;
 
     async fn load(runtime: ContractRuntime<Self>) -> Self {
         let state = ContractsState::load(runtime.root_view_storage_context())
@@ -43,20 +54,18 @@ impl Contract for ContractsContract {
 
     async fn instantiate(&mut self, argument: Self::InstantiationArgument) {
         // Validate parameters are set
-        let _params = self.runtime.application_parameters();
+        let params = self.runtime.application_parameters();
+        assert!(params.max_exposure_bps <= 10_000, "max_exposure_bps cannot exceed the whole bankroll");
+        assert!(params.blackjack_rules.is_valid(), "Blackjack rules need 1-8 decks, 1-4 split hands and a shoe that covers every seat");
+        assert!(params.shoe.is_valid(), "Shoes need 1-8 decks and a penetration of 1-100%");
 
         self.state.default_buy_in.set(argument.starting_balance);
         
-        let master_seed = if argument.random_seed == 0 {
-            0x9e3779b185ebca87
-        } else {
-            argument.random_seed
-        };
-        self.state.master_seed.set(master_seed);
-        
-        // If this chain IS the bank (chain_id == params.bank_chain_id), init house
+        // If this chain IS the bank (chain_id == params.bank_chain_id), init house.
+        // Games can only start once the operator has committed server seeds.
         if self.is_bank_chain() {
             self.state.house_balance.set(100000); // 100k chips for house
+            self.state.faucet_pool.set(argument.faucet_pool);
             self.state.game_counter.set(0);
         }
     }
@@ -69,8 +78,16 @@ impl Contract for ContractsContract {
                 self.handle_request_chips(signer).await;
             }
             
-            Operation::PlayBlackjack { bet } => {
-                self.handle_play_blackjack(signer, bet).await;
+            Operation::RequestSeedCommitment => {
+                self.handle_request_seed_commitment();
+            }
+            
+            Operation::PlayBlackjack { bet, client_seed, nonce, side_bets } => {
+                self.handle_play_blackjack(signer, vec![bet], side_bets.unwrap_or_default(), ClientSeed::from_parts(client_seed, nonce)).await;
+            }
+            
+            Operation::PlayBlackjackMulti { bets, client_seed, nonce } => {
+                self.handle_play_blackjack(signer, bets, SideBets::default(), ClientSeed::from_parts(client_seed, nonce)).await;
             }
             
             Operation::Hit => {
@@ -85,56 +102,109 @@ impl Contract for ContractsContract {
                 self.handle_double_down(signer).await;
             }
 
-            Operation::PlayRoulette { bets } => {
-                self.handle_play_roulette(signer, bets).await;
+            Operation::PlayRoulette { bets, client_seed, nonce } => {
+                self.handle_play_roulette(signer, bets, ClientSeed::from_parts(client_seed, nonce)).await;
             }
 
-            Operation::ReportRouletteResult { game_id, claimed_outcome } => {
-                self.handle_report_roulette_result(signer, game_id, claimed_outcome).await;
+            Operation::PlayBaccarat { amount, bet_type, client_seed, nonce, side_bets } => {
+                self.handle_play_baccarat(signer, vec![BaccaratWager { bet_type, amount }], side_bets.unwrap_or_default(), ClientSeed::from_parts(client_seed, nonce)).await;
             }
 
-            Operation::PlayBaccarat { amount, bet_type } => {
-                self.handle_play_baccarat(signer, amount, bet_type).await;
+            Operation::PlayBaccaratMulti { wagers, client_seed, nonce, side_bets } => {
+                self.handle_play_baccarat(signer, wagers, side_bets.unwrap_or_default(), ClientSeed::from_parts(client_seed, nonce)).await;
             }
 
             Operation::Split => {
                 self.handle_split(signer).await;
             }
 
+            Operation::Insurance => {
+                self.handle_insurance(signer).await;
+            }
+
+            Operation::EvenMoney => {
+                self.handle_even_money(signer).await;
+            }
+
+            Operation::Surrender => {
+                self.handle_surrender(signer).await;
+            }
 
+            Operation::AbandonGame => {
+                self.handle_abandon_game().await;
+            }
+
+            Operation::ClaimUnrevealed => {
+                self.handle_claim_unrevealed().await;
+            }
+
+            Operation::CommitSeeds { commitments } => {
+                self.handle_commit_seeds(commitments);
+            }
+
+            Operation::RevealCards { game_id, cards } => {
+                self.handle_reveal_cards(game_id, cards).await;
+            }
+
+            Operation::RevealSeed { game_id, server_seed } => {
+                self.handle_reveal_seed(game_id, server_seed).await;
+            }
         }
     }
 
     async fn execute_message(&mut self, message: Self::Message) {
+        // Every message is sent with tracking, so a rejected one comes back to its sender
+        if self.runtime.message_is_bouncing() == Some(true) {
+            self.handle_bounced_message(message).await;
+            return;
+        }
+        
+        // Bank → Player messages are only trusted from CasinoParams.bank_chain_id;
+        // Player → Bank messages are checked against the player chain they act for
+        if message.is_from_bank() {
+            self.assert_from_bank();
+        } else {
+            assert!(self.is_bank_chain(), "Player requests must be sent to the Bank chain");
+        }
+        
         match message {
             // ═══════════════════════════════════════════════════════════════
             // BANK RECEIVES FROM PLAYER
             // ═══════════════════════════════════════════════════════════════
             
             Message::RequestChips { player, player_chain } => {
+                self.assert_from_player_chain(player_chain);
                 self.bank_handle_request_chips(player, player_chain).await;
             }
             
-            Message::RequestGame { player, player_chain, game_type, bet } => {
-                self.bank_handle_request_game(player, player_chain, game_type, bet).await;
+            Message::RequestSeedCommitment { player_chain } => {
+                self.assert_from_player_chain(player_chain);
+                self.bank_handle_request_seed_commitment(player_chain).await;
             }
             
-            Message::ReportResult { game_id, player, actions } => {
-                self.bank_handle_report_result(game_id, player, actions).await;
+            Message::RequestGame { player, player_chain, game_type, bets, commitment, client_seed, side_bets } => {
+                self.assert_from_player_chain(player_chain);
+                self.bank_handle_request_game(player, player_chain, game_type, bets, side_bets, commitment, client_seed).await;
             }
-
-            Message::RequestRouletteGame { player, player_chain, bets } => {
-                self.bank_handle_request_roulette(player, player_chain, bets).await;
+            
+            Message::PlayerAction { game_id, player, action, stake } => {
+                self.bank_handle_player_action(game_id, player, action, stake).await;
             }
 
-            Message::ReportRouletteResult { game_id, claimed_outcome } => {
-                self.bank_handle_report_roulette_result(game_id, claimed_outcome).await;
+            Message::RequestRouletteGame { player, player_chain, bets, commitment, client_seed } => {
+                self.assert_from_player_chain(player_chain);
+                self.bank_handle_request_roulette(player, player_chain, bets, commitment, client_seed).await;
             }
 
-            Message::RequestBaccaratGame { player, player_chain, amount, bet_type } => {
-                self.bank_handle_request_baccarat(player, player_chain, amount, bet_type).await;
+            Message::RequestBaccaratGame { player, player_chain, wagers, commitment, client_seed, side_bets } => {
+                self.assert_from_player_chain(player_chain);
+                self.bank_handle_request_baccarat(player, player_chain, wagers, side_bets, commitment, client_seed).await;
             }
 
+            Message::ClaimUnrevealed { player_chain } => {
+                self.assert_from_player_chain(player_chain);
+                self.bank_handle_claim_unrevealed(player_chain).await;
+            }
 
             
             // ═══════════════════════════════════════════════════════════════
@@ -145,23 +215,25 @@ impl Contract for ContractsContract {
                 self.player_handle_chips_granted(amount).await;
             }
             
-            Message::GameReady { game_id, seed, bet } => {
-                self.player_handle_game_ready(game_id, seed, bet).await;
+            Message::SeedCommitted { commitment } => {
+                self.state.seed_commitment.set(Some(commitment));
             }
             
-            Message::GameSettled { game_id, result, payout, dealer_hand } => {
-                self.player_handle_game_settled(game_id, result, payout, dealer_hand).await;
+            Message::GameReady { game_id, commitment, bets, player_hands, dealer_up_card, side_bets } => {
+                self.player_handle_game_ready(game_id, commitment, bets, player_hands, dealer_up_card, side_bets).await;
             }
 
-            Message::RouletteGameReady { game_id, seed, bets } => {
-                self.player_handle_roulette_ready(game_id, seed, bets).await;
+            Message::CardsRevealed { game_id, action, cards } => {
+                self.player_handle_cards_revealed(game_id, action, cards).await;
             }
-
-            Message::RouletteSettled { game_id, outcome, payout, bets } => {
-                self.player_handle_roulette_settled(game_id, outcome, payout, bets).await;
+            
+            Message::GameSettled { game_id, result, payout, player_hands, hands, seats, dealer_hand, commitment, seed, rng_version, client_seed, actions, shoe, side_bets } => {
+                self.player_handle_game_settled(game_id, result, payout, player_hands, hands, seats, dealer_hand, commitment, seed, rng_version, client_seed, actions, shoe, side_bets).await;
             }
 
-
+            Message::RouletteSettled { game_id, outcome, payout, bets, commitment, seed, rng_version, client_seed } => {
+                self.player_handle_roulette_settled(game_id, outcome, payout, bets, commitment, seed, rng_version, client_seed).await;
+            }
 
             Message::BaccaratSettled { 
                 game_id, 
@@ -171,10 +243,24 @@ impl Contract for ContractsContract {
                 banker_hand, 
                 player_score, 
                 banker_score,
-                bet_amount,
-                bet_type
+                wagers,
+                commission_credit,
+                commitment,
+                seed,
+                rng_version,
+                client_seed,
+                shoe,
+                side_bets,
             } => {
-                self.player_handle_baccarat_settled(game_id, winner, payout, player_hand, banker_hand, player_score, banker_score, bet_amount, bet_type).await;
+                self.player_handle_baccarat_settled(game_id, winner, payout, player_hand, banker_hand, player_score, banker_score, wagers, commission_credit, commitment, seed, rng_version, client_seed, shoe, side_bets).await;
+            }
+
+            Message::GameForfeited { game_id, game_type, bet, payout } => {
+                self.player_handle_game_forfeited(game_id, game_type, bet, payout).await;
+            }
+
+            Message::Refund { game_id, game_type, amount } => {
+                self.player_handle_refund(game_id, game_type, amount).await;
             }
         }
     }
@@ -200,6 +286,59 @@ impl ContractsContract {
     fn bank_chain_id(&mut self) -> linera_base::identifiers::ChainId {
         self.runtime.application_parameters().bank_chain_id
     }
+    
+    /// Bank: the incoming message must originate from the player chain it claims to act for
+    fn assert_from_player_chain(&mut self, player_chain: linera_base::identifiers::ChainId) {
+        let origin = self.runtime.message_origin_chain_id()
+            .expect("Incoming message has an origin");
+        assert!(
+            origin == player_chain,
+            "Rejected message from chain {}: it claims to act for player chain {}",
+            origin,
+            player_chain,
+        );
+    }
+    
+    /// Player: the incoming message must originate from the Bank chain
+    fn assert_from_bank(&mut self) {
+        let origin = self.runtime.message_origin_chain_id()
+            .expect("Incoming message has an origin");
+        let bank_chain_id = self.bank_chain_id();
+        assert!(
+            origin == bank_chain_id,
+            "Rejected message from chain {}: only the Bank chain {} may send it",
+            origin,
+            bank_chain_id,
+        );
+    }
+    
+    /// Bank: panic (bouncing the request) unless the house can cover `liability`
+    /// on top of every open game, within `max_exposure_bps` of its balance
+    fn check_liability(&mut self, liability: u64) {
+        let max_exposure_bps = self.runtime.application_parameters().max_exposure_bps;
+        let house = *self.state.house_balance.get();
+        let limit = (house as u128 * max_exposure_bps as u128 / 10_000) as u64;
+        let outstanding = *self.state.outstanding_liability.get();
+        assert!(
+            outstanding.saturating_add(liability) <= limit,
+            "Bank cannot cover this bet: exposure would reach {} of {} allowed",
+            outstanding.saturating_add(liability),
+            limit,
+        );
+    }
+    
+    /// Bank: check and reserve the worst case of a game that stays pending
+    fn reserve_liability(&mut self, liability: u64) {
+        self.check_liability(liability);
+        let outstanding = *self.state.outstanding_liability.get();
+        self.state.outstanding_liability.set(outstanding + liability);
+    }
+    
+    /// Bank: a pending game was settled or cancelled
+    fn release_liability(&mut self, liability: u64) {
+        let outstanding = *self.state.outstanding_liability.get();
+        self.state.outstanding_liability.set(outstanding.saturating_sub(liability));
+    }
 }
 
 // ============================================================================
@@ -221,18 +360,48 @@ impl ContractsContract {
             .send_to(bank_chain_id);
     }
     
-    /// Player starts a Blackjack game - deducts bet and sends to Bank
-    async fn handle_play_blackjack(&mut self, signer: linera_base::identifiers::AccountOwner, bet: u64) {
-        assert!(ALLOWED_BETS.contains(&bet), "Bet must be 1, 2, 3, 4, or 5");
+    /// Player asks the Bank for the seed commitment of its next game
+    fn handle_request_seed_commitment(&mut self) {
+        let bank_chain_id = self.bank_chain_id();
+        let player_chain = self.runtime.chain_id();
         
+        self.runtime
+            .prepare_message(Message::RequestSeedCommitment { player_chain })
+            .with_tracking()
+            .send_to(bank_chain_id);
+    }
+    
+    /// Player: use up the commitment the Bank reserved for the next game
+    fn take_seed_commitment(&mut self) -> String {
+        let commitment = self.state.seed_commitment.get().clone()
+            .expect("Request a seed commitment first");
+        self.state.seed_commitment.set(None);
+        commitment
+    }
+    
+    /// Player starts a Blackjack game on one seat per bet - deducts the bets and sends to Bank
+    async fn handle_play_blackjack(
+        &mut self,
+        signer: linera_base::identifiers::AccountOwner,
+        bets: Vec<u64>,
+        side_bets: SideBets,
+        client_seed: Option<ClientSeed>,
+    ) {
+        let rules = self.runtime.application_parameters().blackjack_rules;
+        validate_seat_bets(&bets, &ALLOWED_BETS, &rules)
+            .unwrap_or_else(|error| panic!("Invalid blackjack bet: {}", error));
+        assert_side_bets(&bets, &side_bets, &rules);
+        
+        let stake = bets.iter().sum::<u64>() + side_bets.total();
         let balance = *self.state.player_balance.get();
-        assert!(balance >= bet, "Insufficient balance");
+        assert!(balance >= stake, "Insufficient balance");
         
         // Check no active game
         assert!(self.state.current_game.get().is_none(), "Game already in progress");
         
-        // Deduct bet (escrow)
-        self.state.player_balance.set(balance - bet);
+        // Deduct bet and side bets (escrow)
+        self.state.player_balance.set(balance - stake);
+        let commitment = self.take_seed_commitment();
         
         // Send request to Bank
         let bank_chain_id = self.bank_chain_id();
@@ -243,143 +412,143 @@ impl ContractsContract {
                 player: signer,
                 player_chain,
                 game_type: GameType::Blackjack,
-                bet,
+                bets,
+                commitment,
+                client_seed,
+                side_bets,
             })
             .with_tracking()
             .send_to(bank_chain_id);
     }
     
-    /// Player hits - draws a card for the current active hand
-    async fn handle_hit(&mut self, _signer: linera_base::identifiers::AccountOwner) {
-        let mut game = self.state.current_game.get().clone()
+    /// Player hits - asks the Bank to reveal a card for the active hand
+    async fn handle_hit(&mut self, signer: linera_base::identifiers::AccountOwner) {
+        let game = self.state.current_game.get().clone()
             .expect("No active game");
         assert!(game.phase == GamePhase::PlayerTurn, "Not your turn");
         
-        let hand_idx = game.active_hand_index as usize;
-        let card = game.deck.pop().expect("Deck empty");
-        game.player_hands[hand_idx].push(card);
-        game.actions.push(GameAction::Hit);
-        
-        let hand_value = calculate_hand_value(&game.player_hands[hand_idx]);
-        
-        if hand_value > 21 {
-            // This hand busted. Move to next hand or finish.
-            self.move_to_next_hand_or_finish(&mut game).await;
-        } else {
-            self.state.current_game.set(Some(game));
-        }
+        self.send_player_action(signer, game, GameAction::Hit);
     }
     
     /// Player stands - finishes the current active hand
-    async fn handle_stand(&mut self, _signer: linera_base::identifiers::AccountOwner) {
-        let mut game = self.state.current_game.get().clone()
+    async fn handle_stand(&mut self, signer: linera_base::identifiers::AccountOwner) {
+        let game = self.state.current_game.get().clone()
             .expect("No active game");
         assert!(game.phase == GamePhase::PlayerTurn, "Not your turn");
         
-        game.actions.push(GameAction::Stand);
-        self.move_to_next_hand_or_finish(&mut game).await;
+        self.send_player_action(signer, game, GameAction::Stand);
     }
     
     /// Player doubles down - double bet for current hand, draw one card, then stand
-    async fn handle_double_down(&mut self, _signer: linera_base::identifiers::AccountOwner) {
-        let mut game = self.state.current_game.get().clone()
+    async fn handle_double_down(&mut self, signer: linera_base::identifiers::AccountOwner) {
+        let game = self.state.current_game.get().clone()
             .expect("No active game");
         assert!(game.phase == GamePhase::PlayerTurn, "Not your turn");
         
-        let hand_idx = game.active_hand_index as usize;
-        assert!(game.player_hands[hand_idx].len() == 2, "Double down only allowed on first 2 cards");
+        assert!(self.offers(&game, GameAction::DoubleDown), "Cannot double down on this hand");
         
         // Check if player has enough balance to double (using the original hand bet)
+        let bet = active_bet(&game);
         let balance = *self.state.player_balance.get();
-        assert!(balance >= game.bet, "Insufficient balance to double");
-        
-        // Deduct the additional bet
-        self.state.player_balance.set(balance - game.bet);
-        // Note: For multi-hand, we might want to track bets per hand, but for now 
-        // we'll keep it simple: the escrowed bet is the BASE bet. 
-        // We'll just increase the total reported bet in the action list if needed, 
-        // or let the bank calculate it based on actions.
+        assert!(balance >= bet, "Insufficient balance to double");
         
-        // Draw exactly one card
-        let card = game.deck.pop().expect("Deck empty");
-        game.player_hands[hand_idx].push(card);
-        game.actions.push(GameAction::DoubleDown);
+        // Deduct the additional bet; the Bank doubles this hand's stake when it replays the action
+        self.state.player_balance.set(balance - bet);
         
-        self.move_to_next_hand_or_finish(&mut game).await;
+        self.send_player_action(signer, game, GameAction::DoubleDown);
     }
 
     /// Player splits - splits a pair into two hands
-    async fn handle_split(&mut self, _signer: linera_base::identifiers::AccountOwner) {
-        let mut game = self.state.current_game.get().clone()
+    async fn handle_split(&mut self, signer: linera_base::identifiers::AccountOwner) {
+        let game = self.state.current_game.get().clone()
             .expect("No active game");
         assert!(game.phase == GamePhase::PlayerTurn, "Not your turn");
         
-        let hand_idx = game.active_hand_index as usize;
-        let hand = &game.player_hands[hand_idx];
-        assert!(hand.len() == 2, "Can only split with exactly 2 cards");
-        
-        // Standard blackjack split: cards must have same value
-        // We'll use get_card_numeric_value equivalent here
-        fn get_val(c: &Card) -> u8 {
-            match c.value.as_str() {
-                "ace" => 11,
-                "king" | "queen" | "jack" => 10,
-                v => v.parse().unwrap_or(0),
-            }
-        }
-        assert!(get_val(&hand[0]) == get_val(&hand[1]), "Cards must have same value to split");
+        // Two cards of the same value, within the rules' split limit
+        assert!(self.offers(&game, GameAction::Split), "Cannot split this hand");
         
         // Check balance for additional bet
+        let bet = active_bet(&game);
         let balance = *self.state.player_balance.get();
-        assert!(balance >= game.bet, "Insufficient balance to split");
-        self.state.player_balance.set(balance - game.bet);
+        assert!(balance >= bet, "Insufficient balance to split");
+        self.state.player_balance.set(balance - bet);
         
-        // Perform the split
-        let card2 = game.player_hands[hand_idx].pop().unwrap();
-        let new_hand = vec![card2];
+        self.send_player_action(signer, game, GameAction::Split);
+    }
+
+    /// Player insures against a dealer natural - escrows half the bet
+    async fn handle_insurance(&mut self, signer: linera_base::identifiers::AccountOwner) {
+        let game = self.state.current_game.get().clone()
+            .expect("No active game");
+        assert!(game.phase == GamePhase::PlayerTurn, "Not your turn");
+        assert!(self.offers(&game, GameAction::Insurance), "Insurance is not on offer");
         
-        // Add new hand to the list (standard: new hand is added to the end)
-        game.player_hands.push(new_hand);
+        let stake = insurance_stake(active_bet(&game));
+        let balance = *self.state.player_balance.get();
+        assert!(balance >= stake, "Insufficient balance to insure");
+        self.state.player_balance.set(balance - stake);
         
-        // Draw new cards for BOTH hands
-        let draw1 = game.deck.pop().expect("Deck empty");
-        game.player_hands[hand_idx].push(draw1);
+        self.send_player_action(signer, game, GameAction::Insurance);
+    }
+
+    /// Player takes even money on a natural against a dealer ace - the Bank settles 1:1
+    async fn handle_even_money(&mut self, signer: linera_base::identifiers::AccountOwner) {
+        let game = self.state.current_game.get().clone()
+            .expect("No active game");
+        assert!(game.phase == GamePhase::PlayerTurn, "Not your turn");
+        assert!(self.offers(&game, GameAction::EvenMoney), "Even money is not on offer");
         
-        let last_idx = game.player_hands.len() - 1;
-        let draw2 = game.deck.pop().expect("Deck empty");
-        game.player_hands[last_idx].push(draw2);
+        self.send_player_action(signer, game, GameAction::EvenMoney);
+    }
+
+    /// Player surrenders the initial hand - the Bank returns half the bet
+    async fn handle_surrender(&mut self, signer: linera_base::identifiers::AccountOwner) {
+        let game = self.state.current_game.get().clone()
+            .expect("No active game");
+        assert!(game.phase == GamePhase::PlayerTurn, "Not your turn");
+        assert!(self.offers(&game, GameAction::Surrender), "Surrender is not on offer");
         
-        game.actions.push(GameAction::Split);
-        self.state.current_game.set(Some(game));
+        self.send_player_action(signer, game, GameAction::Surrender);
     }
 
-    /// Internal: Move to the next hand or finish the round
-    async fn move_to_next_hand_or_finish(&mut self, game: &mut ActiveGame) {
-        // Move to the next hand if one exists
-        if (game.active_hand_index as usize) + 1 < game.player_hands.len() {
-            game.active_hand_index += 1;
-            self.state.current_game.set(Some(game.clone()));
-        } else {
-            // All hands done - report to Bank
-            game.phase = GamePhase::RoundComplete;
-            self.state.current_game.set(Some(game.clone()));
-            
-            let bank_chain_id = self.bank_chain_id();
-            let player = self.runtime.authenticated_signer().expect("Must be signed");
-            
-            self.runtime
-                .prepare_message(Message::ReportResult {
-                    game_id: game.game_id,
-                    player,
-                    actions: game.actions.clone(),
-                })
-                .with_tracking()
-                .send_to(bank_chain_id);
-        }
+    /// Whether `action` is currently on offer for the active hand
+    fn offers(&mut self, game: &ActiveGame, action: GameAction) -> bool {
+        let rules = self.runtime.application_parameters().blackjack_rules;
+        available_actions(
+            &game.player_hands,
+            &game.hands,
+            game.active_hand_index as usize,
+            &game.dealer_hand[0],
+            &game.actions,
+            &rules,
+        ).contains(&action)
+    }
+
+    /// Internal: Send an action to the Bank and wait for the operator to reveal the drawn cards
+    fn send_player_action(&mut self, player: linera_base::identifiers::AccountOwner, mut game: ActiveGame, action: GameAction) {
+        let game_id = game.game_id;
+        let stake = action_stake(action, active_bet(&game));
+        game.phase = GamePhase::AwaitingCards;
+        self.state.current_game.set(Some(game));
+        
+        let bank_chain_id = self.bank_chain_id();
+        
+        self.runtime
+            .prepare_message(Message::PlayerAction {
+                game_id,
+                player,
+                action,
+                stake,
+            })
+            .with_tracking()
+            .send_to(bank_chain_id);
     }
 
     /// Player starts a Roulette game - deducts total bet and sends to Bank
-    async fn handle_play_roulette(&mut self, signer: linera_base::identifiers::AccountOwner, bets: Vec<RouletteBet>) {
+    async fn handle_play_roulette(&mut self, signer: linera_base::identifiers::AccountOwner, bets: Vec<RouletteBet>, client_seed: Option<ClientSeed>) {
+        roulette::validate_bets(&bets)
+            .unwrap_or_else(|error| panic!("Invalid roulette bet: {}", error));
+        
         let total_bet: u64 = bets.iter().map(|b| b.amount).sum();
         
         let balance = *self.state.player_balance.get();
@@ -387,6 +556,7 @@ impl ContractsContract {
         
         // Deduct bet (escrow)
         self.state.player_balance.set(balance - total_bet);
+        let commitment = self.take_seed_commitment();
         
         // Send request to Bank
         let bank_chain_id = self.bank_chain_id();
@@ -397,12 +567,69 @@ impl ContractsContract {
                 player: signer,
                 player_chain,
                 bets,
+                commitment,
+                client_seed,
             })
             .with_tracking()
             .send_to(bank_chain_id);
     }
 }
 
+impl ContractsContract {
+    /// Player clears a stuck blackjack game past its deadline. The stake stays
+    /// with the Bank, which settles the game once the operator reveals its seed.
+    async fn handle_abandon_game(&mut self) {
+        let timeouts = self.runtime.application_parameters().timeouts;
+        let now = self.runtime.system_time().micros();
+        let game = self.state.current_game.get().clone()
+            .filter(|game| timeouts.decision_expired(game.started_at, now))
+            .expect("No game past its deadline");
+        self.state.current_game.set(None);
+        
+        // The Bank still settles it later; the payout then gets its own entry
+        let record = GameRecord {
+            game_id: game.game_id,
+            game_type: game.game_type,
+            player_hands: vec![],
+            hands: Vec::new(),
+            seats: Vec::new(),
+            dealer_hand: vec![],
+            bet: game.bet,
+            result: GameResult::Abandoned,
+            payout: 0,
+            timestamp: now,
+            seed: None,
+            rng_version: None,
+            client_seed: None,
+            commitment: Some(game.commitment),
+            actions: Vec::new(),
+            roulette_bets: None,
+            roulette_outcome: None,
+            baccarat_winner: None,
+            baccarat_bet: None,
+            baccarat_wagers: Vec::new(),
+            baccarat_commission_credit: 0,
+            baccarat_side_bets: Vec::new(),
+            shoe: None,
+            blackjack_rules: None,
+            baccarat_variant: None,
+            side_bets: Vec::new(),
+        };
+        self.state.game_history.push(record);
+    }
+    
+    /// Player asks the Bank to forfeit every game the operator left unrevealed past its deadline
+    async fn handle_claim_unrevealed(&mut self) {
+        let bank_chain_id = self.bank_chain_id();
+        let player_chain = self.runtime.chain_id();
+        
+        self.runtime
+            .prepare_message(Message::ClaimUnrevealed { player_chain })
+            .with_tracking()
+            .send_to(bank_chain_id);
+    }
+}
+
 // ============================================================================
 // PLAYER-SIDE MESSAGE HANDLERS
 // ============================================================================
@@ -414,150 +641,145 @@ impl ContractsContract {
         self.state.player_balance.set(balance + amount);
     }
     
-    /// Player receives game seed from Bank - deal cards and start playing
-    async fn player_handle_game_ready(&mut self, game_id: u64, seed: u64, bet: u64) {
-        // Create shuffled deck using the seed
-        let mut deck = create_deck();
-        shuffle(&mut deck, seed);
-        
-        // Deal initial cards
-        let player_card1 = deck.pop().unwrap();
-        let player_card2 = deck.pop().unwrap();
-        let dealer_up = deck.pop().unwrap();
-        let dealer_hole = deck.pop().unwrap();
-        
-        let player_hand = vec![player_card1, player_card2];
-        
-        let phase = GamePhase::PlayerTurn;
-        let actions = Vec::new();
-        
-        // Check for instant blackjack (not currently used but kept for future logic)
-        let player_value = calculate_hand_value(&player_hand);
-        let dealer_full_hand = vec![dealer_up.clone(), dealer_hole.clone()];
-        let dealer_value = calculate_hand_value(&dealer_full_hand);
-        
-        let _player_has_blackjack = player_value == 21;
-        let _dealer_has_blackjack = dealer_value == 21;
-
-        let mut dealer_hand = vec![dealer_up.clone()];
-        if phase == GamePhase::RoundComplete {
-            dealer_hand.push(dealer_hole.clone());
-        }
+    /// Player receives the seed commitment and the visible cards from Bank
+    async fn player_handle_game_ready(
+        &mut self,
+        game_id: u64,
+        commitment: String,
+        bets: Vec<u64>,
+        player_hands: Vec<Vec<Card>>,
+        dealer_up_card: Card,
+        side_bets: Vec<SideBetResult>,
+    ) {
+        // Bounces back to the Bank if another game is running; that game then
+        // stays pending there and settles once the operator reveals its seed
+        assert!(self.state.current_game.get().is_none(), "Game already in progress");
         
+        // Seats are played in order; seat naturals already stand. A round that is
+        // over at the deal is never dealt: it settles straight from the seed.
+        let hands = seat_hands(&player_hands, &bets);
+        let active_hand_index = next_open_hand(&hands) as u32;
         let game = ActiveGame {
             game_id,
-            seed,
-            bet,
+            commitment,
+            bet: bets.iter().sum(),
             game_type: GameType::Blackjack,
-            phase,
-            player_hands: vec![player_hand], // Initial single hand in hands list
-            active_hand_index: 0,
-            dealer_hand,
-            dealer_hole_card: Some(dealer_hole),
-            deck,
-            actions: actions.clone(),
+            started_at: self.runtime.system_time().micros(),
+            phase: GamePhase::PlayerTurn,
+            player_hands,
+            hands,
+            active_hand_index,
+            dealer_hand: vec![dealer_up_card],
+            actions: Vec::new(),
+            insurance: 0,
+            side_bets,
         };
         
-        self.state.current_game.set(Some(game.clone()));
-        
-        // If the game is complete, auto-report
-        if phase == GamePhase::RoundComplete {
-            let bank_chain_id = self.bank_chain_id();
-            if let Some(player) = self.runtime.authenticated_signer() {
-                self.runtime
-                    .prepare_message(Message::ReportResult {
-                        game_id,
-                        player,
-                        actions,
-                    })
-                    .with_tracking()
-                    .send_to(bank_chain_id);
-            }
+        self.state.current_game.set(Some(game));
+    }
+
+    /// Player receives chips back from the Bank after a bounced settlement
+    async fn player_handle_refund(&mut self, game_id: u64, game_type: GameType, amount: u64) {
+        // Drop the game this refund voids so new games can start
+        if self.state.current_game.get().as_ref().is_some_and(|g| g.game_id == game_id) {
+            self.state.current_game.set(None);
         }
+        
+        self.refund_player(game_id, game_type, amount, amount);
+    }
+
+    /// Player receives the cards the Bank drew for the last action
+    async fn player_handle_cards_revealed(&mut self, game_id: u64, action: GameAction, cards: Vec<Card>) {
+        let mut game = self.state.current_game.get().clone()
+            .expect("No active game");
+        assert!(game.game_id == game_id, "Game ID mismatch");
+        assert!(game.phase == GamePhase::AwaitingCards, "No action awaiting cards");
+        
+        // The Bank only reveals cards while the round goes on, so this is
+        // `PlayerTurn` again; the last action's cards come with the settlement
+        let rules = self.runtime.application_parameters().blackjack_rules;
+        apply_revealed(&mut game, action, cards, &rules);
+        
+        self.state.current_game.set(Some(game));
     }
     
     /// Player receives game result from Bank
-    async fn player_handle_game_settled(&mut self, game_id: u64, result: GameResult, payout: u64, dealer_hand: Vec<Card>) {
+    #[allow(clippy::too_many_arguments)]
+    async fn player_handle_game_settled(
+        &mut self,
+        game_id: u64,
+        result: GameResult,
+        payout: u64,
+        player_hands: Vec<Vec<Card>>,
+        hands: Vec<HandState>,
+        seats: Vec<SeatResult>,
+        dealer_hand: Vec<Card>,
+        commitment: String,
+        seed: String,
+        rng_version: RngVersion,
+        client_seed: Option<ClientSeed>,
+        actions: Vec<GameAction>,
+        shoe: Shoe,
+        side_bets: Vec<SideBetResult>,
+    ) {
         // Credit payout to player
+        let side_payout: u64 = side_bets.iter().map(|side_bet| side_bet.payout).sum();
         let balance = *self.state.player_balance.get();
-        self.state.player_balance.set(balance + payout);
+        self.state.player_balance.set(balance + payout + side_payout);
         
-        // Record in history
-        if let Some(game) = self.state.current_game.get().clone() {
-            if game.game_id == game_id {
-                let now = self.runtime.system_time().micros();
-                
-                let record = GameRecord {
-                    game_id,
-                    game_type: game.game_type,
-                    player_hands: game.player_hands.clone(),
-                    dealer_hand: dealer_hand, // Use full dealer hand from Bank
-                    bet: game.bet,
-                    result,
-                    payout,
-                    timestamp: now,
-                    roulette_bets: None,
-                    roulette_outcome: None,
-                    baccarat_winner: None,
-                    baccarat_bet: None,
-                };
-                self.state.game_history.push(record);
-            }
+        // A late settlement of an abandoned game must not touch the game now in progress
+        if self.state.current_game.get().as_ref().is_some_and(|game| game.game_id == game_id) {
+            self.state.current_game.set(None);
         }
         
-        // Clear current game
-        self.state.current_game.set(None);
-    }
-
-    /// Player receives roulette seed from Bank - calculate outcome locally
-    async fn player_handle_roulette_ready(&mut self, game_id: u64, seed: u64, bets: Vec<RouletteBet>) {
-        // Calculate outcome locally using same RNG as bank
-        let mut rng = SimpleRng::new(seed);
-        let outcome = (rng.next() % 37) as u8;
-        
-        // Store pending roulette game for UI to query
-        let pending = PendingRouletteGame {
+        // Record in history; an abandoned game gets a second entry with its payout.
+        // Parameters are fixed for the application, so these are the rules it was dealt under
+        let rules = self.runtime.application_parameters().blackjack_rules;
+        let now = self.runtime.system_time().micros();
+        let record = GameRecord {
             game_id,
-            seed,
-            bets: bets.clone(),
-            outcome,
+            game_type: GameType::Blackjack,
+            player_hands, // Final hands from Bank (includes the settling draw)
+            bet: seats.iter().map(|seat| seat.bet).sum(),
+            hands,
+            seats,
+            dealer_hand, // Use full dealer hand from Bank
+            result,
+            payout,
+            timestamp: now,
+            seed: Some(seed),
+            rng_version: Some(rng_version),
+            client_seed,
+            commitment: Some(commitment),
+            actions,
+            roulette_bets: None,
+            roulette_outcome: None,
+            baccarat_winner: None,
+            baccarat_bet: None,
+            baccarat_wagers: Vec::new(),
+            baccarat_commission_credit: 0,
+            baccarat_side_bets: Vec::new(),
+            shoe: Some(shoe),
+            blackjack_rules: Some(rules),
+            baccarat_variant: None,
+            side_bets,
         };
-        self.state.pending_roulette.set(Some(pending));
-        
-        // Send verification to bank (bank has player info stored from RequestRouletteGame)
-        let bank_chain_id = self.bank_chain_id();
-        
-        self.runtime
-            .prepare_message(Message::ReportRouletteResult {
-                game_id,
-                claimed_outcome: outcome,
-            })
-            .with_tracking()
-            .send_to(bank_chain_id);
-    }
-
-    /// Player sends roulette result to bank for verification (called by operation if auto-send fails)
-    async fn handle_report_roulette_result(&mut self, _signer: linera_base::identifiers::AccountOwner, game_id: u64, claimed_outcome: u8) {
-        // Get pending roulette game
-        let pending = self.state.pending_roulette.get().clone()
-            .expect("No pending roulette game");
-        assert!(pending.game_id == game_id, "Game ID mismatch");
-        assert!(pending.outcome == claimed_outcome, "Outcome mismatch");
-        
-        // Send to bank
-        let bank_chain_id = self.bank_chain_id();
-        
-        self.runtime
-            .prepare_message(Message::ReportRouletteResult {
-                game_id,
-                claimed_outcome,
-            })
-            .with_tracking()
-            .send_to(bank_chain_id);
+        self.state.game_history.push(record);
     }
-
+    
     /// Player receives roulette settlement from Bank
-    async fn player_handle_roulette_settled(&mut self, game_id: u64, outcome: u8, payout: u64, bets: Vec<RouletteBet>) {
+    #[allow(clippy::too_many_arguments)]
+    async fn player_handle_roulette_settled(
+        &mut self,
+        game_id: u64,
+        outcome: u8,
+        payout: u64,
+        bets: Vec<RouletteBet>,
+        commitment: String,
+        seed: String,
+        rng_version: RngVersion,
+        client_seed: Option<ClientSeed>,
+    ) {
         // Credit payout to player
         let balance = *self.state.player_balance.get();
         self.state.player_balance.set(balance + payout);
@@ -570,29 +792,54 @@ impl ContractsContract {
             game_id,
             game_type: GameType::Roulette,
             player_hands: vec![],
+            hands: Vec::new(),
+            seats: Vec::new(),
             dealer_hand: vec![],
             bet: total_bet,
             result: if payout > 0 { GameResult::PlayerWin } else { GameResult::DealerWin },
             payout,
             timestamp: now,
+            seed: Some(seed),
+            rng_version: Some(rng_version),
+            client_seed,
+            commitment: Some(commitment),
+            actions: Vec::new(),
             roulette_bets: Some(bets),
             roulette_outcome: Some(outcome),
             baccarat_winner: None,
             baccarat_bet: None,
+            baccarat_wagers: Vec::new(),
+            baccarat_commission_credit: 0,
+            baccarat_side_bets: Vec::new(),
+            shoe: None,
+            blackjack_rules: None,
+            baccarat_variant: None,
+            side_bets: Vec::new(),
         };
         self.state.game_history.push(record);
-        
-        // Clear pending roulette
-        self.state.pending_roulette.set(None);
     }
 
-    /// Player starts a Baccarat game - deducts bet and sends to Bank
-    async fn handle_play_baccarat(&mut self, signer: linera_base::identifiers::AccountOwner, amount: u64, bet_type: BaccaratBetType) {
+    /// Player starts a Baccarat game - deducts bets and side bets and sends to Bank
+    async fn handle_play_baccarat(
+        &mut self,
+        signer: linera_base::identifiers::AccountOwner,
+        wagers: Vec<BaccaratWager>,
+        side_bets: Vec<BaccaratSideBet>,
+        client_seed: Option<ClientSeed>,
+    ) {
+        validate_baccarat_wagers(&wagers)
+            .unwrap_or_else(|error| panic!("Invalid baccarat bet: {}", error));
+        let amount = baccarat_wager_stakes(&wagers);
+        validate_baccarat_side_bets(&side_bets, amount)
+            .unwrap_or_else(|error| panic!("Invalid baccarat side bet: {}", error));
+        
+        let stake = baccarat_total_stake(&wagers, &side_bets);
         let balance = *self.state.player_balance.get();
-        assert!(balance >= amount, "Insufficient balance");
+        assert!(balance >= stake, "Insufficient balance");
         
-        // Deduct bet (escrow)
-        self.state.player_balance.set(balance - amount);
+        // Deduct bets and side bets (escrow)
+        self.state.player_balance.set(balance - stake);
+        let commitment = self.take_seed_commitment();
         
         let bank_chain_id = self.bank_chain_id();
         let player_chain = self.runtime.chain_id();
@@ -601,19 +848,17 @@ impl ContractsContract {
             .prepare_message(Message::RequestBaccaratGame {
                 player: signer,
                 player_chain,
-                amount,
-                bet_type,
+                wagers,
+                commitment,
+                client_seed,
+                side_bets,
             })
             .with_tracking()
             .send_to(bank_chain_id);
     }
     
-    /// Player receives Baccarat seed from Bank
-    /// Player receives Baccarat seed from Bank (Legacy/Unused - Baccarat is now Bank-Authoritative)
-    /// This is removed as part of the refactor.
-
-    
     /// Player receives Baccarat settlement from Bank
+    #[allow(clippy::too_many_arguments)]
     async fn player_handle_baccarat_settled(
         &mut self,
         game_id: u64,
@@ -623,12 +868,19 @@ impl ContractsContract {
         banker_hand: Vec<Card>,
         _player_score: u8,
         _banker_score: u8,
-        bet_amount: u64,
-        bet_type: BaccaratBetType,
+        wagers: Vec<BaccaratWagerResult>,
+        commission_credit: u64,
+        commitment: String,
+        seed: String,
+        rng_version: RngVersion,
+        client_seed: Option<ClientSeed>,
+        shoe: Shoe,
+        side_bets: Vec<BaccaratSideBetResult>,
     ) {
-        // Credit payout
+        // Credit payout, side bets included
+        let side_payout: u64 = side_bets.iter().map(|side_bet| side_bet.payout).sum();
         let balance = *self.state.player_balance.get();
-        self.state.player_balance.set(balance + payout);
+        self.state.player_balance.set(balance + payout + side_payout);
         
         let result = match winner {
             BaccaratBetType::Player => GameResult::PlayerWin, // Approximate mapping
@@ -636,26 +888,86 @@ impl ContractsContract {
             BaccaratBetType::Tie => GameResult::Push,
         };
         
+        let variant = self.runtime.application_parameters().baccarat_variant;
         let now = self.runtime.system_time().micros();
         
         let record = GameRecord {
             game_id,
             game_type: GameType::Baccarat,
             player_hands: vec![player_hand], 
+            hands: Vec::new(),
+            seats: Vec::new(),
             dealer_hand: banker_hand, // Map banker hand to dealer hand field
-            bet: bet_amount,
+            bet: wagers.iter().map(|wager| wager.amount).sum(),
             result, // This is lossy, maybe we should update GameRecord too?
             payout,
             timestamp: now,
+            seed: Some(seed),
+            rng_version: Some(rng_version),
+            client_seed,
+            commitment: Some(commitment),
+            actions: Vec::new(),
             roulette_bets: None,
             roulette_outcome: None,
-        baccarat_winner: Some(winner),
-            baccarat_bet: Some(bet_type),
+            baccarat_winner: Some(winner),
+            baccarat_bet: match wagers.as_slice() {
+                [wager] => Some(wager.bet_type),
+                _ => None,
+            },
+            baccarat_wagers: wagers,
+            baccarat_commission_credit: commission_credit,
+            baccarat_side_bets: side_bets,
+            shoe: Some(shoe),
+            blackjack_rules: None,
+            baccarat_variant: Some(variant),
+            side_bets: Vec::new(),
+        };
+        self.state.game_history.push(record);
+    }
+    
+    /// Player is paid for a game the operator did not reveal in time
+    async fn player_handle_game_forfeited(&mut self, game_id: u64, game_type: GameType, bet: u64, payout: u64) {
+        let balance = *self.state.player_balance.get();
+        self.state.player_balance.set(balance + payout);
+        
+        let commitment = self.state.current_game.get().clone()
+            .filter(|game| game.game_id == game_id)
+            .map(|game| game.commitment);
+        if commitment.is_some() {
+            self.state.current_game.set(None);
+        }
+        
+        let now = self.runtime.system_time().micros();
+        let record = GameRecord {
+            game_id,
+            game_type,
+            player_hands: vec![],
+            hands: Vec::new(),
+            seats: Vec::new(),
+            dealer_hand: vec![],
+            bet,
+            result: GameResult::Forfeited,
+            payout,
+            timestamp: now,
+            seed: None,
+            rng_version: None,
+            client_seed: None,
+            commitment,
+            actions: Vec::new(),
+            roulette_bets: None,
+            roulette_outcome: None,
+            baccarat_winner: None,
+            baccarat_bet: None,
+            baccarat_wagers: Vec::new(),
+            baccarat_commission_credit: 0,
+            baccarat_side_bets: Vec::new(),
+            shoe: None,
+            blackjack_rules: None,
+            baccarat_variant: None,
+            side_bets: Vec::new(),
         };
         self.state.game_history.push(record);
     }
-
-
 }
 
 // ============================================================================
@@ -663,9 +975,26 @@ impl ContractsContract {
 // ============================================================================
 
 impl ContractsContract {
-    /// Bank grants chips to player
+    /// Bank grants chips to player from the faucet pool, within the faucet limits
     async fn bank_handle_request_chips(&mut self, player: linera_base::identifiers::AccountOwner, player_chain: linera_base::identifiers::ChainId) {
         let amount = *self.state.default_buy_in.get();
+        let config = self.runtime.application_parameters().faucet;
+        let now = self.runtime.system_time().micros();
+        
+        let mut claim = self.state.faucet_claims.get(&player).await
+            .expect("Failed to get faucet claim")
+            .unwrap_or_default();
+        let next_claim_at = claim.next_claim_at(&config, amount)
+            .expect("Faucet lifetime cap reached");
+        assert!(now >= next_claim_at, "Faucet cooldown active until {}", next_claim_at);
+        
+        let pool = *self.state.faucet_pool.get();
+        assert!(pool >= amount, "Faucet pool is empty");
+        self.state.faucet_pool.set(pool - amount);
+        
+        claim.last_claim_at = now;
+        claim.total_claimed += amount;
+        self.state.faucet_claims.insert(&player, claim).expect("Failed to store faucet claim");
         
         // Send chips to player
         self.runtime
@@ -674,136 +1003,284 @@ impl ContractsContract {
             .send_to(player_chain);
     }
     
-    /// Bank receives game request - generate seed and store pending game
+    /// Bank reserves the player chain the oldest commitment the operator has
+    /// published, or sends the one it already holds again
+    async fn bank_handle_request_seed_commitment(&mut self, player_chain: linera_base::identifiers::ChainId) {
+        let reserved = self.state.seed_reservations.get(&player_chain).await
+            .expect("Failed to get seed reservation");
+        let commitment = match reserved {
+            Some(commitment) => commitment,
+            None => {
+                let commitment = self.state.seed_commitments.front().await
+                    .expect("Failed to read seed commitments")
+                    .expect("The Bank has no seed commitments left");
+                self.state.seed_commitments.delete_front();
+                self.state.seed_reservations.insert(&player_chain, commitment.clone())
+                    .expect("Failed to store seed reservation");
+                commitment
+            }
+        };
+        
+        self.runtime
+            .prepare_message(Message::SeedCommitted { commitment })
+            .with_tracking()
+            .send_to(player_chain);
+    }
+    
+    /// Bank receives game request - bind it to the seed commitment reserved for it and wait for the operator to deal
+    #[allow(clippy::too_many_arguments)]
     async fn bank_handle_request_game(
         &mut self,
         player: linera_base::identifiers::AccountOwner,
         player_chain: linera_base::identifiers::ChainId,
         game_type: GameType,
-        bet: u64,
+        bets: Vec<u64>,
+        side_bets: SideBets,
+        commitment: String,
+        client_seed: Option<ClientSeed>,
     ) {
-        // Generate unique game ID
-        let game_id = *self.state.game_counter.get();
-        self.state.game_counter.set(game_id + 1);
+        let rules = self.runtime.application_parameters().blackjack_rules;
+        validate_seat_bets(&bets, &ALLOWED_BETS, &rules)
+            .unwrap_or_else(|error| panic!("Invalid blackjack bet: {}", error));
+        assert_side_bets(&bets, &side_bets, &rules);
         
-        // Generate deterministic seed using master seed + game_id + player + TIMESTAMP
-        let master_seed = *self.state.master_seed.get();
+        let game_id = self.next_game_id();
+        self.take_seed_reservation(player_chain, &commitment).await;
         let now = self.runtime.system_time().micros();
-        let seed = generate_game_seed(master_seed, game_id, &player, now);
         
-        // Store pending game
+        // Reject (and bounce the escrow back) if the house could not pay the worst case
+        let max_liability = bets.iter().map(|&bet| blackjack_max_liability(bet, &rules)).sum::<u64>()
+            + side_bets_max_liability(&side_bets);
+        self.reserve_liability(max_liability);
+        let (shoe_table, shoe) = self.shoe_for_round(GameType::Blackjack, game_id, bets.len());
+        
+        // The operator deals with `RevealCards`, or settles a round over at the deal with `RevealSeed`
+        let bet = bets.iter().sum::<u64>();
         let pending = PendingGame {
             player,
             player_chain,
             game_type,
             bet,
-            seed,
+            commitment,
+            rng_version: RngVersion::CURRENT,
+            client_seed,
             created_at: now,
+            waiting_since: now,
+            staked: bet + side_bets.total(),
+            max_liability,
+            actions: Vec::new(),
+            round: None,
+            shoe: Some(shoe),
+            shoe_table,
+            side_bets,
+            seat_bets: bets,
+            baccarat_wagers: Vec::new(),
+            baccarat_side_bets: Vec::new(),
         };
         self.state.pending_games.insert(&game_id, pending).expect("Failed to insert pending game");
-        
-        // Send seed to player
-        self.runtime
-            .prepare_message(Message::GameReady { game_id, seed, bet })
-            .with_tracking()
-            .send_to(player_chain);
     }
     
-    /// Bank receives result - verify and settle
-    async fn bank_handle_report_result(
+    /// Bank receives a blackjack action - check it against the cards shown and wait for the operator to reveal its cards
+    async fn bank_handle_player_action(
         &mut self,
         game_id: u64,
         player: linera_base::identifiers::AccountOwner,
-        actions: Vec<GameAction>,
+        action: GameAction,
+        stake: u64,
     ) {
         // Get pending game
-        let pending = self.state.pending_games.get(&game_id).await
+        let mut pending = self.state.pending_games.get(&game_id).await
             .expect("Failed to get pending game")
             .expect("Game not found");
         
         assert!(pending.player == player, "Not your game");
+        self.assert_from_player_chain(pending.player_chain);
+        assert!(pending.game_type == GameType::Blackjack, "Not a blackjack game");
+        
+        // Rejecting the action bounces its stake back
+        let mut round = pending.round.take().expect("The round has not been dealt yet");
+        assert!(round.phase == GamePhase::PlayerTurn, "The cards of the last action are not revealed yet");
+        let rules = self.runtime.application_parameters().blackjack_rules;
+        let offered = available_actions(
+            &round.player_hands,
+            &round.hands,
+            round.active_hand_index as usize,
+            &round.dealer_hand[0],
+            &round.actions,
+            &rules,
+        );
+        assert!(offered.contains(&action), "{:?} is not on offer", action);
+        
+        // Doubles and splits must arrive with the active hand's bet escrowed, insurance with half of it
+        assert!(stake == action_stake(action, active_bet(&round)), "Escrowed stake does not match the action");
+        
+        round.phase = GamePhase::AwaitingCards;
+        pending.round = Some(round);
+        pending.actions.push(action);
+        pending.staked += stake;
+        pending.waiting_since = self.runtime.system_time().micros();
+        self.state.pending_games.insert(&game_id, pending)
+            .expect("Failed to update pending game");
+    }
+    
+    /// Bank receives a claim on games the operator left unrevealed - forfeit those past the deadline
+    async fn bank_handle_claim_unrevealed(&mut self, player_chain: linera_base::identifiers::ChainId) {
+        let timeouts = self.runtime.application_parameters().timeouts;
+        let now = self.runtime.system_time().micros();
+        let game_ids = self.state.pending_games.indices().await
+            .expect("Failed to list pending games");
+        
+        let mut forfeited = 0;
+        for game_id in game_ids {
+            let pending = self.state.pending_games.get(&game_id).await
+                .expect("Failed to get pending game")
+                .expect("Game not found");
+            if pending.player_chain == player_chain
+                && pending.awaits_operator()
+                && timeouts.reveal_expired(pending.waiting_since, now)
+            {
+                self.forfeit_game(game_id, pending).await;
+                forfeited += 1;
+            }
+        }
+        assert!(forfeited > 0, "No game is past its reveal deadline");
+    }
+    
+    /// Bank: pay the stakes back with the most the game could have won, and
+    /// rebuild its shoe since the cards it drew are never revealed
+    async fn forfeit_game(&mut self, game_id: u64, pending: PendingGame) {
+        let payout = pending.staked + pending.max_liability;
+        let house = *self.state.house_balance.get();
+        self.state.house_balance.set(house.saturating_sub(pending.max_liability));
+        
+        if pending.shoe.is_some() {
+            self.discard_from_shoe(pending.game_type, pending.shoe_table, &[], true);
+        }
+        if pending.game_type == GameType::Roulette {
+            self.state.pending_roulette_bets.remove(&game_id).expect("Failed to remove pending bets");
+        }
+        self.state.pending_games.remove(&game_id).expect("Failed to remove pending game");
+        self.release_liability(pending.max_liability);
         
-        // Replay game deterministically
-        let (result, payout, dealer_hand) = self.replay_and_verify(&pending, &actions);
+        self.runtime
+            .prepare_message(Message::GameForfeited {
+                game_id,
+                game_type: pending.game_type,
+                bet: pending.staked,
+                payout,
+            })
+            .with_tracking()
+            .send_to(pending.player_chain);
+    }
+    
+    /// Bank: dealer plays out, house balance is updated and the seed is revealed to the player
+    fn settle_blackjack(
+        &mut self,
+        game_id: u64,
+        pending: PendingGame,
+        mut table: BlackjackTable,
+        side_bets: Vec<SideBetResult>,
+        server_seed: String,
+    ) {
+        let wagered = table.total_bet() + side_bet_stakes(&side_bets);
+        table.play_dealer();
+        let reshuffled = table.reshuffled_mid_round();
+        let (result, payout, player_hands, hands, dealer_hand) = table.settle();
+        let seats = seat_results(&hands, &pending.seat_bets);
+        let shoe = pending.shoe.expect("Blackjack rounds are dealt from a shoe");
+        let used: Vec<Card> = player_hands.iter().flatten().chain(&dealer_hand).cloned().collect();
+        self.discard_from_shoe(GameType::Blackjack, pending.shoe_table, &used, reshuffled);
         
         // Update house balance
+        let paid = payout + side_bet_payouts(&side_bets);
         let house = *self.state.house_balance.get();
-        if payout > pending.bet {
+        if paid > wagered {
             // House pays winnings
-            self.state.house_balance.set(house.saturating_sub(payout - pending.bet));
+            self.state.house_balance.set(house.saturating_sub(paid - wagered));
         } else {
             // House keeps loss
-            self.state.house_balance.set(house + (pending.bet - payout));
+            self.state.house_balance.set(house + (wagered - paid));
         }
         
         // Remove pending game
         self.state.pending_games.remove(&game_id).expect("Failed to remove pending game");
+        self.release_liability(pending.max_liability);
         
-        // Send result to player (including full dealer hand)
+        // Send result to player, revealing the seed so the game can be audited
         self.runtime
-            .prepare_message(Message::GameSettled { game_id, result, payout, dealer_hand })
+            .prepare_message(Message::GameSettled {
+                game_id,
+                result,
+                payout,
+                player_hands,
+                hands,
+                seats,
+                dealer_hand,
+                commitment: pending.commitment,
+                seed: server_seed,
+                rng_version: pending.rng_version,
+                client_seed: pending.client_seed,
+                actions: pending.actions,
+                shoe,
+                side_bets,
+            })
             .with_tracking()
             .send_to(pending.player_chain);
     }
 
-    /// Bank receives roulette game request - generate seed and send back
+    /// Bank receives roulette game request - bind it to the seed commitment reserved for it and wait for the seed
     async fn bank_handle_request_roulette(
         &mut self,
         player: linera_base::identifiers::AccountOwner,
         player_chain: linera_base::identifiers::ChainId,
         bets: Vec<RouletteBet>,
+        commitment: String,
+        client_seed: Option<ClientSeed>,
     ) {
-        // Generate unique game ID
-        let game_id = *self.state.game_counter.get();
-        self.state.game_counter.set(game_id + 1);
+        // Reject malformed bets before the escrow is accepted
+        roulette::validate_bets(&bets)
+            .unwrap_or_else(|error| panic!("Invalid roulette bet: {}", error));
         
-        // Generate seed for this game
-        let master_seed = *self.state.master_seed.get();
+        let game_id = self.next_game_id();
+        self.take_seed_reservation(player_chain, &commitment).await;
         let now = self.runtime.system_time().micros();
-        let seed = generate_game_seed(master_seed, game_id, &player, now);
+        
+        // Reject (and bounce the escrow back) if the house could not pay the best number
+        let max_liability = roulette_max_liability(&bets);
+        self.reserve_liability(max_liability);
         
         // Store pending game
+        let bet = bets.iter().map(|b| b.amount).sum();
         self.state.pending_games.insert(&game_id, PendingGame {
             player,
             player_chain,
             game_type: GameType::Roulette,
-            bet: bets.iter().map(|b| b.amount).sum(),
-            seed,
+            bet,
+            commitment,
+            rng_version: RngVersion::CURRENT,
+            client_seed,
             created_at: now,
+            waiting_since: now,
+            staked: bet,
+            max_liability,
+            actions: Vec::new(),
+            round: None,
+            shoe: None,
+            shoe_table: 0,
+            side_bets: SideBets::default(),
+            seat_bets: Vec::new(),
+            baccarat_wagers: Vec::new(),
+            baccarat_side_bets: Vec::new(),
         }).expect("Failed to insert pending game");
         
         // Store bets separately for payout calculation
-        self.state.pending_roulette_bets.insert(&game_id, bets.clone())
+        self.state.pending_roulette_bets.insert(&game_id, bets)
             .expect("Failed to insert pending bets");
-        
-        // Send seed to player (player will calculate outcome locally)
-        self.runtime
-            .prepare_message(Message::RouletteGameReady { 
-                game_id, 
-                seed, 
-                bets 
-            })
-            .with_tracking()
-            .send_to(player_chain);
     }
-
-    /// Bank verifies and settles roulette game
-    async fn bank_handle_report_roulette_result(
-        &mut self,
-        game_id: u64,
-        claimed_outcome: u8,
-    ) {
-        // Get pending game
-        let pending = self.state.pending_games.get(&game_id).await
-            .expect("Failed to get pending game")
-            .expect("Game not found");
-        
-        // Game_id is unique and already tied to the player from RequestRouletteGame
-        
-        // Verify outcome
-        let mut rng = SimpleRng::new(pending.seed);
-        let expected_outcome = (rng.next() % 37) as u8;
-        assert!(claimed_outcome == expected_outcome, "Outcome verification failed");
+    
+    /// Bank: pay the stored bets against the seeded spin and notify the player
+    async fn settle_roulette(&mut self, game_id: u64, pending: PendingGame, server_seed: String) {
+        let expected_outcome = spin_roulette(game_seed(&server_seed, pending.client_seed.as_ref()), pending.rng_version);
         
         // Get stored bets
         let bets = self.state.pending_roulette_bets.get(&game_id).await
@@ -825,6 +1302,7 @@ impl ContractsContract {
         // Remove pending game and bets
         self.state.pending_games.remove(&game_id).expect("Failed to remove pending game");
         self.state.pending_roulette_bets.remove(&game_id).expect("Failed to remove pending bets");
+        self.release_liability(pending.max_liability);
         
         // Send settlement to player
         self.runtime
@@ -833,57 +1311,102 @@ impl ContractsContract {
                 outcome: expected_outcome, 
                 payout,
                 bets,
+                commitment: pending.commitment,
+                seed: server_seed,
+                rng_version: pending.rng_version,
+                client_seed: pending.client_seed,
             })
             .with_tracking()
-            .with_tracking()
             .send_to(pending.player_chain);
     }
 
-    /// Bank receives Baccarat game request - Runs game and settles immediately
+    /// Bank receives Baccarat game request - bind it to the seed commitment reserved for it and a shoe, and wait for the seed
     async fn bank_handle_request_baccarat(
         &mut self,
         player: linera_base::identifiers::AccountOwner,
         player_chain: linera_base::identifiers::ChainId,
-        amount: u64,
-        bet_type: BaccaratBetType,
+        wagers: Vec<BaccaratWager>,
+        side_bets: Vec<BaccaratSideBet>,
+        commitment: String,
+        client_seed: Option<ClientSeed>,
     ) {
-        // Generate unique game ID
-        let game_id = *self.state.game_counter.get();
-        self.state.game_counter.set(game_id + 1);
-        
-        // Generate seed
-        let master_seed = *self.state.master_seed.get();
+        validate_baccarat_wagers(&wagers)
+            .unwrap_or_else(|error| panic!("Invalid baccarat bet: {}", error));
+        let amount = baccarat_wager_stakes(&wagers);
+        validate_baccarat_side_bets(&side_bets, amount)
+            .unwrap_or_else(|error| panic!("Invalid baccarat side bet: {}", error));
+        
+        let game_id = self.next_game_id();
+        self.take_seed_reservation(player_chain, &commitment).await;
         let now = self.runtime.system_time().micros();
-        let seed = generate_game_seed(master_seed, game_id, &player, now);
-        
-        // Run logic immediately
-        let (actual_winner, player_hand, banker_hand, player_score, banker_score) = run_baccarat_game(seed);
-        
-        // Calculate payout
-        let mut payout = 0;
-        if actual_winner == bet_type {
-            if bet_type == BaccaratBetType::Tie {
-                payout = amount * 9; // 8:1 payout = 9x total return
-            } else if bet_type == BaccaratBetType::Player {
-                payout = amount * 2; // 1:1 payout
-            } else if bet_type == BaccaratBetType::Banker {
-                // 0.95:1 payout (5% commission)
-                payout = amount + (amount * 95 / 100);
-            }
-        } else if actual_winner == BaccaratBetType::Tie && bet_type != BaccaratBetType::Tie {
-            // Push on Tie
-            payout = amount;
+        
+        // Reject (and bounce the escrow back) if the house could not pay the worst case
+        let variant = self.runtime.application_parameters().baccarat_variant;
+        let max_liability = baccarat_wagers_max_liability(variant, &wagers)
+            .checked_add(baccarat_side_bets_max_liability(&side_bets))
+            .expect("Baccarat liability overflows");
+        self.reserve_liability(max_liability);
+        let (shoe_table, shoe) = self.shoe_for_round(GameType::Baccarat, game_id, 1);
+        
+        self.state.pending_games.insert(&game_id, PendingGame {
+            player,
+            player_chain,
+            game_type: GameType::Baccarat,
+            bet: amount,
+            commitment,
+            rng_version: RngVersion::CURRENT,
+            client_seed,
+            created_at: now,
+            waiting_since: now,
+            staked: baccarat_total_stake(&wagers, &side_bets),
+            max_liability,
+            actions: Vec::new(),
+            round: None,
+            shoe: Some(shoe),
+            shoe_table,
+            side_bets: SideBets::default(),
+            seat_bets: Vec::new(),
+            baccarat_wagers: wagers,
+            baccarat_side_bets: side_bets,
+        }).expect("Failed to insert pending game");
+    }
+    
+    /// Bank: deal the coup from the seed, pay it and notify the player
+    async fn settle_baccarat(&mut self, game_id: u64, pending: PendingGame, server_seed: String) {
+        let variant = self.runtime.application_parameters().baccarat_variant;
+        let shoe = pending.shoe.expect("Baccarat coups are dealt from a shoe");
+        let seed = game_seed(&server_seed, pending.client_seed.as_ref());
+        let (actual_winner, player_hand, banker_hand, player_score, banker_score) = run_baccarat_game(&shoe, seed, pending.rng_version);
+        let used: Vec<Card> = player_hand.iter().chain(&banker_hand).cloned().collect();
+        self.discard_from_shoe(GameType::Baccarat, pending.shoe_table, &used, false);
+        
+        // Calculate payouts, carrying commission fractions for the player
+        let player = pending.player;
+        let commission_credit = self.state.baccarat_commission_credit.get(&player).await
+            .expect("Failed to get commission credit")
+            .unwrap_or(0);
+        let (wagers, credit) = settle_baccarat_wagers(variant, &pending.baccarat_wagers, actual_winner, &banker_hand, commission_credit);
+        if credit == 0 {
+            self.state.baccarat_commission_credit.remove(&player).expect("Failed to clear commission credit");
+        } else {
+            self.state.baccarat_commission_credit.insert(&player, credit).expect("Failed to store commission credit");
         }
+        let payout = wagers.iter().map(|wager| wager.payout).sum::<u64>();
+        let side_bets = settle_baccarat_side_bets(&pending.baccarat_side_bets, &player_hand, &banker_hand);
 
         // Update house balance
+        let wagered = pending.staked;
+        let paid = payout + side_bets.iter().map(|side_bet| side_bet.payout).sum::<u64>();
         let house = *self.state.house_balance.get();
-        if payout > amount {
-            self.state.house_balance.set(house.saturating_sub(payout - amount));
+        if paid > wagered {
+            self.state.house_balance.set(house.saturating_sub(paid - wagered));
         } else {
-            self.state.house_balance.set(house + (amount - payout));
+            self.state.house_balance.set(house + (wagered - paid));
         }
         
-        // Send settlement directly to player
+        self.state.pending_games.remove(&game_id).expect("Failed to remove pending game");
+        self.release_liability(pending.max_liability);
+        
         self.runtime
             .prepare_message(Message::BaccaratSettled {
                 game_id,
@@ -893,369 +1416,504 @@ impl ContractsContract {
                 banker_hand,
                 player_score,
                 banker_score,
-                bet_amount: amount,
-                bet_type,
+                wagers,
+                commission_credit,
+                commitment: pending.commitment,
+                seed: server_seed,
+                rng_version: pending.rng_version,
+                client_seed: pending.client_seed,
+                shoe,
+                side_bets,
             })
             .with_tracking()
-            .send_to(player_chain);
+            .send_to(pending.player_chain);
     }
     
-    /// Replay game with given seed and actions, return result, payout, and dealer hand
-    fn replay_and_verify(&self, pending: &PendingGame, actions: &[GameAction]) -> (GameResult, u64, Vec<Card>) {
-        // Recreate deck with same seed
-        let mut deck = create_deck();
-        shuffle(&mut deck, pending.seed);
-        
-        // Deal initial cards (same order as player)
-        let player_card1 = deck.pop().unwrap();
-        let player_card2 = deck.pop().unwrap();
-        let dealer_up = deck.pop().unwrap();
-        let dealer_hole = deck.pop().unwrap();
-        
-        let initial_player_hand = vec![player_card1, player_card2];
-        let mut player_hands = vec![initial_player_hand];
-        let initial_dealer_hand = vec![dealer_up, dealer_hole];
-        let mut dealer_hand = initial_dealer_hand.clone();
-        
-        // Initial Blackjack Check (matches player_handle_game_ready)
-        let player_val = calculate_hand_value(&player_hands[0]);
-        let dealer_val = calculate_hand_value(&dealer_hand);
-        
-        if player_val == 21 || dealer_val == 21 {
-            // Instant settlement
-            if player_val == 21 && dealer_val != 21 {
-                return (GameResult::PlayerBlackjack, pending.bet * 5 / 2, dealer_hand);
-            } else if dealer_val == 21 && player_val != 21 {
-                return (GameResult::DealerWin, 0, dealer_hand);
-            } else {
-                return (GameResult::Push, pending.bet, dealer_hand);
-            }
+    /// Bank: hand out the next game ID
+    fn next_game_id(&mut self) -> u64 {
+        let game_id = *self.state.game_counter.get();
+        self.state.game_counter.set(game_id + 1);
+        game_id
+    }
+    
+    /// Bank: use up the commitment reserved for `player_chain`, which the
+    /// player sent back with its game request
+    async fn take_seed_reservation(&mut self, player_chain: linera_base::identifiers::ChainId, commitment: &str) {
+        let reserved = self.state.seed_reservations.get(&player_chain).await
+            .expect("Failed to get seed reservation")
+            .expect("No seed commitment is reserved for this chain");
+        assert!(reserved == commitment, "The game's seed commitment is not the one reserved for this chain");
+        self.state.seed_reservations.remove(&player_chain).expect("Failed to remove seed reservation");
+    }
+    
+    /// Bank: hand a new round of `game_type` the first free table and its shoe,
+    /// rebuilt first once its cut card is out or it is too short for the round's
+    /// seats. The table stays with the round until it settles.
+    fn shoe_for_round(&mut self, game_type: GameType, game_id: u64, seats: usize) -> (u32, Shoe) {
+        let params = self.runtime.application_parameters();
+        let decks = match game_type {
+            GameType::Blackjack => params.blackjack_rules.decks,
+            GameType::Baccarat => params.shoe.baccarat_decks,
+            GameType::Roulette => panic!("Roulette has no shoe"),
+        };
+        let mut tables = self.shoe_register(game_type).get().clone();
+        // Every shoe in use: open another table rather than share its cards
+        let index = tables.iter().position(|table| table.round.is_none()).unwrap_or_else(|| {
+            tables.push(ShoeTable { shoe: Shoe::new(decks), round: None });
+            tables.len() - 1
+        });
+        if tables[index].shoe.past_cut_card(params.shoe.penetration_percent, seats) {
+            self.reshuffle_shoe(game_type, index, &mut tables[index].shoe);
         }
+        tables[index].round = Some(game_id);
+        let shoe = tables[index].shoe.clone();
+        self.shoe_register(game_type).set(tables);
+        (index as u32, shoe)
+    }
+    
+    /// Bank: take the cards a finished round used out of its table's shoe and
+    /// free the table. If the round ran the shoe out and dealt on from its
+    /// discards, the shoe is rebuilt instead.
+    fn discard_from_shoe(&mut self, game_type: GameType, table: u32, cards: &[Card], reshuffled: bool) {
+        let mut tables = self.shoe_register(game_type).get().clone();
+        let mut shoe_table = tables.get(table as usize).cloned().expect("Round has no shoe table");
+        if reshuffled {
+            self.reshuffle_shoe(game_type, table as usize, &mut shoe_table.shoe);
+        } else {
+            shoe_table.shoe.remove(cards)
+                .unwrap_or_else(|error| panic!("Round dealt a card its shoe did not hold: {}", error));
+        }
+        shoe_table.round = None;
+        tables[table as usize] = shoe_table;
+        self.shoe_register(game_type).set(tables);
+    }
+    
+    /// Bank: rebuild a shoe from full decks and announce it
+    fn reshuffle_shoe(&mut self, game_type: GameType, table: usize, shoe: &mut Shoe) {
+        let cards_dealt = shoe.dealt() as u32;
+        shoe.reshuffle();
+        self.runtime.emit(
+            StreamName(SHOE_STREAM.to_vec()),
+            &CasinoEvent::ShoeReshuffled { game_type, table: table as u32, shuffles: shoe.shuffles, cards_dealt },
+        );
+    }
+    
+    fn shoe_register(&mut self, game_type: GameType) -> &mut RegisterView<Vec<ShoeTable>> {
+        match game_type {
+            GameType::Blackjack => &mut self.state.blackjack_shoes,
+            GameType::Baccarat => &mut self.state.baccarat_shoes,
+            GameType::Roulette => panic!("Roulette has no shoe"),
+        }
+    }
+}
+
+// ============================================================================
+// BANK-SIDE OPERATION HANDLERS
+// ============================================================================
 
-        // Replay player actions
-        let mut active_idx = 0;
-        let mut bets = vec![pending.bet]; // Track bet for each hand
+impl ContractsContract {
+    /// Bank publishes commitments to server seeds the operator keeps off-chain
+    fn handle_commit_seeds(&mut self, commitments: Vec<String>) {
+        assert!(self.is_bank_chain(), "Only the Bank chain takes seed commitments");
+        for commitment in commitments {
+            assert!(is_hex_32_bytes(&commitment), "Seed commitments are SHA-256 digests in lowercase hex");
+            self.state.seed_commitments.push_back(commitment);
+        }
+    }
+    
+    /// Bank shows the deal of a blackjack round, or the cards drawn for the action it last accepted
+    async fn handle_reveal_cards(&mut self, game_id: u64, cards: Vec<Card>) {
+        assert!(self.is_bank_chain(), "Only the Bank chain reveals cards");
+        let mut pending = self.state.pending_games.get(&game_id).await
+            .expect("Failed to get pending game")
+            .expect("Game not found");
+        assert!(pending.game_type == GameType::Blackjack, "Not a blackjack game");
         
-        for action in actions {
-            if active_idx >= player_hands.len() { break; }
-            
-            match action {
-                GameAction::Hit => {
-                    let card = deck.pop().expect("Deck empty during replay");
-                    player_hands[active_idx].push(card);
-                    if calculate_hand_value(&player_hands[active_idx]) > 21 {
-                        active_idx += 1;
-                    }
-                }
-                GameAction::Stand => {
-                    active_idx += 1;
-                }
-                GameAction::DoubleDown => {
-                    let card = deck.pop().expect("Deck empty during replay");
-                    player_hands[active_idx].push(card);
-                    bets[active_idx] *= 2;
-                    active_idx += 1;
-                }
-                GameAction::Split => {
-                    // Split active hand
-                    let card2 = player_hands[active_idx].pop().unwrap();
-                    let new_hand = vec![card2];
-                    player_hands.push(new_hand);
-                    bets.push(pending.bet);
-                    
-                    // Draw new cards
-                    player_hands[active_idx].push(deck.pop().unwrap());
-                    let last = player_hands.len() - 1;
-                    player_hands[last].push(deck.pop().unwrap());
-                }
+        let rules = self.runtime.application_parameters().blackjack_rules;
+        let now = self.runtime.system_time().micros();
+        let message = match pending.round.take() {
+            None => {
+                let round = dealt_round(game_id, &pending, cards, now, &rules);
+                let message = Message::GameReady {
+                    game_id,
+                    commitment: round.commitment.clone(),
+                    bets: pending.seat_bets.clone(),
+                    player_hands: round.player_hands.clone(),
+                    dealer_up_card: round.dealer_hand[0].clone(),
+                    side_bets: round.side_bets.clone(),
+                };
+                pending.round = Some(round);
+                message
             }
-        }
+            Some(mut round) => {
+                assert!(round.phase == GamePhase::AwaitingCards, "No action is awaiting cards");
+                let action = *pending.actions.last().expect("An action awaits its cards");
+                apply_revealed(&mut round, action, cards.clone(), &rules);
+                assert!(round.phase == GamePhase::PlayerTurn, "The round is over: reveal the seed to settle it");
+                pending.round = Some(round);
+                Message::CardsRevealed { game_id, action, cards }
+            }
+        };
         
-        // Dealer plays if there's any non-busted hand
-        let player_has_active_hand = player_hands.iter().any(|h| calculate_hand_value(h) <= 21);
+        pending.waiting_since = now;
+        let player_chain = pending.player_chain;
+        self.state.pending_games.insert(&game_id, pending)
+            .expect("Failed to update pending game");
         
-        if player_has_active_hand {
-            while calculate_hand_value(&dealer_hand) < 17 {
-                let card = deck.pop().expect("Deck empty during dealer turn");
-                dealer_hand.push(card);
+        self.runtime
+            .prepare_message(message)
+            .with_tracking()
+            .send_to(player_chain);
+    }
+    
+    /// Bank checks a revealed server seed against the game's commitment and settles the game
+    async fn handle_reveal_seed(&mut self, game_id: u64, server_seed: String) {
+        assert!(self.is_bank_chain(), "Only the Bank chain reveals seeds");
+        let mut pending = self.state.pending_games.get(&game_id).await
+            .expect("Failed to get pending game")
+            .expect("Game not found");
+        assert!(is_hex_32_bytes(&server_seed), "Server seeds are 32 bytes in lowercase hex");
+        assert!(
+            verify_seed_commitment(&pending.commitment, &server_seed),
+            "Server seed does not match the game's commitment"
+        );
+        
+        match pending.game_type {
+            GameType::Blackjack => {
+                let (table, side_bets) = self.replay_revealed(&mut pending, &server_seed);
+                self.settle_blackjack(game_id, pending, table, side_bets, server_seed);
             }
+            GameType::Roulette => self.settle_roulette(game_id, pending, server_seed).await,
+            GameType::Baccarat => self.settle_baccarat(game_id, pending, server_seed).await,
         }
+    }
+    
+    /// Bank: replay a blackjack round from its revealed seed, checking every
+    /// card shown along the way, and play it to the end. A round still waiting
+    /// on the player is only ended past the decision deadline, standing every
+    /// open hand. Also returns the side bets settled on the opening cards.
+    fn replay_revealed(&mut self, pending: &mut PendingGame, server_seed: &str) -> (BlackjackTable, Vec<SideBetResult>) {
+        let params = self.runtime.application_parameters();
+        let now = self.runtime.system_time().micros();
+        let shoe = pending.shoe.as_ref().expect("Blackjack rounds are dealt from a shoe");
+        let seed = game_seed(server_seed, pending.client_seed.as_ref());
+        let mut table = BlackjackTable::deal_seats(shoe, seed, pending.rng_version, &pending.seat_bets, params.blackjack_rules);
+        let side_bets = settle_side_bets(&pending.side_bets, &table.player_hands[0], &table.dealer_hand[0]);
+        
+        let Some(round) = &pending.round else {
+            // Only a round over at the deal settles without being dealt
+            assert!(table.is_finished(), "Deal the round before revealing its seed");
+            return (table, side_bets);
+        };
         
-        let dealer_value = calculate_hand_value(&dealer_hand);
-        let mut total_payout = 0;
-
-        for (i, hand) in player_hands.iter().enumerate() {
-            let p_val = calculate_hand_value(hand);
-            let bet = bets[i];
-            
-            if p_val > 21 {
-                // Hand busted
-            } else if dealer_value > 21 {
-                total_payout += bet * 2;
-            } else if p_val > dealer_value {
-                total_payout += bet * 2;
-            } else if p_val == dealer_value {
-                total_payout += bet;
-            } else {
-                // Dealer wins
-            }
+        // Cards may only have been shown while the round went on
+        assert!(!table.is_finished(), "The round was dealt after it was over");
+        for action in &round.actions {
+            table.apply(*action);
+            assert!(!table.is_finished(), "Cards were revealed after the round was over");
         }
-
-        // Determine summary result for record keeping
-        let total_bet: u64 = bets.iter().sum();
-        let result = if total_payout > total_bet {
-            GameResult::PlayerWin
-        } else if total_payout < total_bet {
-            GameResult::DealerWin
+        assert!(
+            table.player_hands == round.player_hands && table.dealer_hand[0] == round.dealer_hand[0],
+            "Revealed cards do not match the seed"
+        );
+        
+        if round.phase == GamePhase::AwaitingCards {
+            let action = *pending.actions.last().expect("An action awaits its cards");
+            table.apply(action);
+            assert!(table.is_finished(), "The round goes on: reveal the cards of the last action");
         } else {
-            GameResult::Push
-        };
-
-        (result, total_payout, dealer_hand)
+            // The player's decision is overdue: every open hand stands, replayed like any other action
+            assert!(
+                params.timeouts.decision_expired(pending.waiting_since, now),
+                "The player still has time to act"
+            );
+            while !table.is_finished() {
+                table.apply(GameAction::Stand);
+                pending.actions.push(GameAction::Stand);
+            }
+        }
+        (table, side_bets)
     }
 }
 
 // ============================================================================
-// HELPER FUNCTIONS
+// BOUNCED MESSAGE HANDLERS
 // ============================================================================
 
-fn create_deck() -> Vec<Card> {
-    let mut deck = Vec::with_capacity(52);
-    for suit in SUITS {
-        for value in VALUES {
-            deck.push(Card::new(suit, value));
-        }
-    }
-    deck
-}
-
-fn shuffle(deck: &mut [Card], seed: u64) {
-    let mut rng = SimpleRng::new(seed);
-    for i in (1..deck.len()).rev() {
-        let j = (rng.next() as usize) % (i + 1);
-        deck.swap(i, j);
-    }
-}
-
-fn generate_game_seed(master_seed: u64, game_id: u64, player: &linera_base::identifiers::AccountOwner, timestamp: u64) -> u64 {
-    use std::hash::{Hash, Hasher};
-    let mut hasher = std::collections::hash_map::DefaultHasher::new();
-    master_seed.hash(&mut hasher);
-    game_id.hash(&mut hasher);
-    player.hash(&mut hasher);
-    timestamp.hash(&mut hasher); // Mix in the timestamp
-    hasher.finish()
-}
-
-fn calculate_hand_value(cards: &[Card]) -> u8 {
-    let mut total = 0u8;
-    let mut aces = 0u8;
-
-    for card in cards {
-        match card.value.as_str() {
-            "ace" => {
-                aces += 1;
-                total = total.saturating_add(11);
+impl ContractsContract {
+    /// A message we sent was rejected by its destination and returned to us.
+    /// Escrowed bets and payouts are restored so no chips are lost in flight;
+    /// a game the Bank has dealt is never voided by the player refusing it.
+    async fn handle_bounced_message(&mut self, message: Message) {
+        match message {
+            // ─────────────────────────────────────────────────────────────────
+            // Player → Bank, bounced back to the player chain
+            // ─────────────────────────────────────────────────────────────────
+            
+            // Nothing was escrowed for a faucet request or a seed commitment
+            Message::RequestChips { .. } | Message::RequestSeedCommitment { .. } => {}
+            
+            // The Bank still holds the reservation, so the next game can use it
+            Message::RequestGame { game_type, bets, side_bets, commitment, .. } => {
+                let stake = bets.iter().sum::<u64>() + side_bets.total();
+                self.refund_player(UNASSIGNED_GAME_ID, game_type, stake, stake);
+                self.state.seed_commitment.set(Some(commitment));
+            }
+            
+            Message::PlayerAction { game_id, stake, .. } => {
+                self.player_handle_action_bounced(game_id, stake);
             }
-            "king" | "queen" | "jack" => total = total.saturating_add(10),
-            value => {
-                let parsed = value.parse::<u8>().unwrap_or(0);
-                total = total.saturating_add(parsed);
+            
+            Message::RequestRouletteGame { bets, commitment, .. } => {
+                let total_bet: u64 = bets.iter().map(|b| b.amount).sum();
+                self.refund_player(UNASSIGNED_GAME_ID, GameType::Roulette, total_bet, total_bet);
+                self.state.seed_commitment.set(Some(commitment));
+            }
+            
+            Message::RequestBaccaratGame { wagers, side_bets, commitment, .. } => {
+                let stake = baccarat_total_stake(&wagers, &side_bets);
+                self.refund_player(UNASSIGNED_GAME_ID, GameType::Baccarat, stake, stake);
+                self.state.seed_commitment.set(Some(commitment));
+            }
+            
+            // Nothing was escrowed for a claim
+            Message::ClaimUnrevealed { .. } => {}
+            
+            // ─────────────────────────────────────────────────────────────────
+            // Bank → Player, bounced back to the bank chain
+            // ─────────────────────────────────────────────────────────────────
+            
+            // The player chain refused the chips, so they go back to the faucet
+            Message::ChipsGranted { player, amount } => {
+                self.bank_return_faucet_chips(player, amount).await;
+            }
+            
+            // The reservation stays and is sent again on the next request
+            Message::SeedCommitted { .. } => {}
+            
+            // The player has already seen these cards, so refusing them must not
+            // void the game: it stays pending and settles once its seed is revealed
+            Message::GameReady { .. } | Message::CardsRevealed { .. } => {}
+            
+            Message::GameSettled { game_id, payout, side_bets, .. } => {
+                self.bank_return_payout(game_id, GameType::Blackjack, payout + side_bet_payouts(&side_bets));
+            }
+            
+            Message::RouletteSettled { game_id, payout, .. } => {
+                self.bank_return_payout(game_id, GameType::Roulette, payout);
+            }
+            
+            Message::BaccaratSettled { game_id, payout, side_bets, .. } => {
+                let side_payout: u64 = side_bets.iter().map(|side_bet| side_bet.payout).sum();
+                self.bank_return_payout(game_id, GameType::Baccarat, payout + side_payout);
+            }
+            
+            Message::GameForfeited { game_id, game_type, payout, .. } => {
+                self.bank_return_payout(game_id, game_type, payout);
+            }
+            
+            // The player chain refused the chips, so the house keeps them
+            Message::Refund { amount, .. } => {
+                let house = *self.state.house_balance.get();
+                self.state.house_balance.set(house + amount);
             }
         }
     }
-
-    while total > 21 && aces > 0 {
-        total -= 10;
-        aces -= 1;
+    
+    /// Bank: put bounced faucet chips back in the pool and un-count the claim.
+    /// The cooldown still runs from the original claim.
+    async fn bank_return_faucet_chips(&mut self, player: linera_base::identifiers::AccountOwner, amount: u64) {
+        let pool = *self.state.faucet_pool.get();
+        self.state.faucet_pool.set(pool + amount);
+        
+        let mut claim: FaucetClaim = self.state.faucet_claims.get(&player).await
+            .expect("Failed to get faucet claim")
+            .unwrap_or_default();
+        claim.total_claimed = claim.total_claimed.saturating_sub(amount);
+        self.state.faucet_claims.insert(&player, claim).expect("Failed to store faucet claim");
     }
-
-    total
-}
-
-fn calculate_roulette_payout(bets: &[RouletteBet], outcome: u8) -> u64 {
-    let mut payout = 0;
-    for bet in bets {
-        let win = match bet.bet_type {
-            RouletteBetType::Number => bet.number == Some(outcome),
-            RouletteBetType::Split => {
-                // Check if outcome is in the numbers array
-                bet.numbers.as_ref().map_or(false, |nums| nums.contains(&outcome))
-            },
-            RouletteBetType::Street => {
-                bet.numbers.as_ref().map_or(false, |nums| nums.contains(&outcome))
-            },
-            RouletteBetType::Corner => {
-                bet.numbers.as_ref().map_or(false, |nums| nums.contains(&outcome))
-            },
-            RouletteBetType::Line => {
-                // Line bet: 6 numbers (2 adjacent streets)
-                bet.numbers.as_ref().map_or(false, |nums| nums.contains(&outcome))
-            },
-            RouletteBetType::Basket => {
-                // First Four: 0, 1, 2, 3
-                outcome == 0 || outcome == 1 || outcome == 2 || outcome == 3
-            },
-            RouletteBetType::Red => {
-                let red_numbers = [1, 3, 5, 7, 9, 12, 14, 16, 18, 19, 21, 23, 25, 27, 30, 32, 34, 36];
-                red_numbers.contains(&outcome)
-            },
-            RouletteBetType::Black => {
-                let black_numbers = [2, 4, 6, 8, 10, 11, 13, 15, 17, 20, 22, 24, 26, 28, 29, 31, 33, 35];
-                black_numbers.contains(&outcome)
-            },
-            RouletteBetType::Even => outcome != 0 && outcome % 2 == 0,
-            RouletteBetType::Odd => outcome != 0 && outcome % 2 != 0,
-            RouletteBetType::Low => outcome >= 1 && outcome <= 18,
-            RouletteBetType::High => outcome >= 19 && outcome <= 36,
-            RouletteBetType::Dozen1 => outcome >= 1 && outcome <= 12,
-            RouletteBetType::Dozen2 => outcome >= 13 && outcome <= 24,
-            RouletteBetType::Dozen3 => outcome >= 25 && outcome <= 36,
-            RouletteBetType::Column1 => outcome != 0 && outcome % 3 == 0,  // 3,6,9,12,15,18,21,24,27,30,33,36
-            RouletteBetType::Column2 => outcome != 0 && outcome % 3 == 2,  // 2,5,8,11,14,17,20,23,26,29,32,35
-            RouletteBetType::Column3 => outcome != 0 && outcome % 3 == 1,  // 1,4,7,10,13,16,19,22,25,28,31,34
+    
+    /// Player: credit returned chips and record the voided game in history
+    fn refund_player(&mut self, game_id: u64, game_type: GameType, bet: u64, amount: u64) {
+        let balance = *self.state.player_balance.get();
+        self.state.player_balance.set(balance + amount);
+        
+        let now = self.runtime.system_time().micros();
+        
+        let record = GameRecord {
+            game_id,
+            game_type,
+            player_hands: vec![],
+            hands: Vec::new(),
+            seats: Vec::new(),
+            dealer_hand: vec![],
+            bet,
+            result: GameResult::Refunded,
+            payout: amount,
+            timestamp: now,
+            seed: None,
+            rng_version: None,
+            client_seed: None,
+            commitment: None,
+            actions: Vec::new(),
+            roulette_bets: None,
+            roulette_outcome: None,
+            baccarat_winner: None,
+            baccarat_bet: None,
+            baccarat_wagers: Vec::new(),
+            baccarat_commission_credit: 0,
+            baccarat_side_bets: Vec::new(),
+            shoe: None,
+            blackjack_rules: None,
+            baccarat_variant: None,
+            side_bets: Vec::new(),
         };
+        self.state.game_history.push(record);
+    }
+    
+    /// Player: the Bank rejected an action - return the extra stake and let the player act again
+    fn player_handle_action_bounced(&mut self, game_id: u64, stake: u64) {
+        // The stake comes back even if the game was settled in the meantime
+        let balance = *self.state.player_balance.get();
+        self.state.player_balance.set(balance + stake);
         
-        if win {
-            let multiplier = match bet.bet_type {
-                RouletteBetType::Number => 36,   // 35:1
-                RouletteBetType::Split => 18,    // 17:1
-                RouletteBetType::Street => 12,   // 11:1
-                RouletteBetType::Corner => 9,    // 8:1
-                RouletteBetType::Line => 6,      // 5:1
-                RouletteBetType::Basket => 7,    // 6:1
-                RouletteBetType::Dozen1 | RouletteBetType::Dozen2 | RouletteBetType::Dozen3 => 3, // 2:1
-                RouletteBetType::Column1 | RouletteBetType::Column2 | RouletteBetType::Column3 => 3, // 2:1
-                RouletteBetType::Red | RouletteBetType::Black | 
-                RouletteBetType::Even | RouletteBetType::Odd | 
-                RouletteBetType::Low | RouletteBetType::High => 2, // 1:1
-            };
-            payout += bet.amount * multiplier;
+        let Some(mut game) = self.state.current_game.get().clone() else {
+            return;
+        };
+        if game.game_id != game_id || game.phase != GamePhase::AwaitingCards {
+            return;
         }
+        
+        game.phase = GamePhase::PlayerTurn;
+        self.state.current_game.set(Some(game));
+    }
+    
+    /// Bank: a settlement bounced - send the payout again as a plain refund
+    fn bank_return_payout(&mut self, game_id: u64, game_type: GameType, payout: u64) {
+        if payout == 0 {
+            return;
+        }
+        let player_chain = self.runtime.message_origin_chain_id()
+            .expect("Bounced message has an origin");
+        
+        self.runtime
+            .prepare_message(Message::Refund {
+                game_id,
+                game_type,
+                amount: payout,
+            })
+            .with_tracking()
+            .send_to(player_chain);
     }
-    payout
 }
 
-struct SimpleRng(u64);
+// ============================================================================
+// STAKE AND DEAL HELPERS
+// ============================================================================
 
-impl SimpleRng {
-    fn new(seed: u64) -> Self {
-        let seed = if seed == 0 { 0x9e3779b185ebca87 } else { seed };
-        SimpleRng(seed)
+/// Chips escrowed with a blackjack action on top of the base bet
+fn action_stake(action: GameAction, bet: u64) -> u64 {
+    match action {
+        GameAction::DoubleDown | GameAction::Split => bet,
+        GameAction::Insurance => insurance_stake(bet),
+        GameAction::Hit | GameAction::Stand | GameAction::EvenMoney | GameAction::Surrender => 0,
     }
+}
 
-    fn next(&mut self) -> u64 {
-        let mut value = self.0;
-        value ^= value << 7;
-        value ^= value >> 9;
-        value ^= value << 8;
-        self.0 = value;
-        self.0
+/// Side bets come with a single seat and may not exceed its bet
+fn assert_side_bets(bets: &[u64], side_bets: &SideBets, rules: &BlackjackRules) {
+    if side_bets.total() == 0 {
+        return;
     }
+    assert!(
+        rules.decks >= SIDE_BET_MIN_DECKS,
+        "Side bets need a shoe of at least {} decks", SIDE_BET_MIN_DECKS
+    );
+    assert!(bets.len() == 1, "Side bets are only taken on a single seat");
+    assert!(
+        side_bets.perfect_pairs <= bets[0] && side_bets.twenty_one_plus_three <= bets[0],
+        "Side bets may not exceed the main bet"
+    );
 }
 
-fn calculate_baccarat_score(cards: &[Card]) -> u8 {
-    let mut score = 0;
-    for c in cards {
-        let val = match c.value.as_str() {
-            "ace" => 1,
-            "2" => 2,
-            "3" => 3,
-            "4" => 4,
-            "5" => 5,
-            "6" => 6,
-            "7" => 7,
-            "8" => 8,
-            "9" => 9,
-            _ => 0, // 10, J, Q, K are 0
-        };
-        score = (score + val) % 10;
-    }
-    score
+fn baccarat_wager_stakes(wagers: &[BaccaratWager]) -> u64 {
+    baccarat_wagers_total(wagers).unwrap_or_else(|error| panic!("Invalid baccarat bet: {}", error))
 }
 
-fn run_baccarat_game(seed: u64) -> (BaccaratBetType, Vec<Card>, Vec<Card>, u8, u8) {
-    let mut deck = create_deck();
-    shuffle(&mut deck, seed);
-    
-    // Draw initial cards
-    let p1 = deck.pop().unwrap();
-    let b1 = deck.pop().unwrap();
-    let p2 = deck.pop().unwrap();
-    let b2 = deck.pop().unwrap();
-    
-    let mut player_hand = vec![p1, p2];
-    let mut banker_hand = vec![b1, b2];
-    
-    let mut p_score = calculate_baccarat_score(&player_hand);
-    let mut b_score = calculate_baccarat_score(&banker_hand);
-    
-    // Natural win check (8 or 9)
-    if p_score >= 8 || b_score >= 8 {
-        let winner = if p_score > b_score {
-            BaccaratBetType::Player
-        } else if b_score > p_score {
-            BaccaratBetType::Banker
-        } else {
-            BaccaratBetType::Tie
-        };
-        return (winner, player_hand, banker_hand, p_score, b_score);
-    }
-    
-    // Player draw rules
-    let mut p_third = None;
-    if p_score <= 5 {
-        let c = deck.pop().unwrap();
-        
-        // Calculate third card value for Banker rule
-        let val = match c.value.as_str() {
-            "ace" => 1,
-            "2" => 2,
-            "3" => 3,
-            "4" => 4,
-            "5" => 5,
-            "6" => 6,
-            "7" => 7,
-            "8" => 8,
-            "9" => 9,
-            _ => 0,
-        };
-        p_third = Some(val);
-        
-        player_hand.push(c);
-        p_score = calculate_baccarat_score(&player_hand);
+/// Main bets plus side bets, rejected if the sum overflows
+fn baccarat_total_stake(wagers: &[BaccaratWager], side_bets: &[BaccaratSideBet]) -> u64 {
+    baccarat_side_bets_total(side_bets)
+        .ok()
+        .and_then(|side_stake| baccarat_wager_stakes(wagers).checked_add(side_stake))
+        .expect("Invalid baccarat side bet: Stakes overflow")
+}
+
+/// Bet on the hand being played, which doubles and splits match
+fn active_bet(game: &ActiveGame) -> u64 {
+    game.hands.get(game.active_hand_index as usize).map_or(game.bet, |hand| hand.bet)
+}
+
+fn side_bet_stakes(side_bets: &[SideBetResult]) -> u64 {
+    side_bets.iter().map(|side_bet| side_bet.stake).sum()
+}
+
+fn side_bet_payouts(side_bets: &[SideBetResult]) -> u64 {
+    side_bets.iter().map(|side_bet| side_bet.payout).sum()
+}
+
+/// Apply `action` to `game` with the cards revealed for it. Every card must be used.
+fn apply_revealed(game: &mut ActiveGame, action: GameAction, cards: Vec<Card>, rules: &BlackjackRules) {
+    let mut revealed = cards.into_iter();
+    let next_idx = apply_blackjack_action(
+        &mut game.player_hands,
+        &mut game.hands,
+        game.active_hand_index as usize,
+        action,
+        rules,
+        || revealed.next().expect("Too few cards revealed"),
+    );
+    assert!(revealed.next().is_none(), "Too many cards revealed");
+    game.active_hand_index = next_idx as u32;
+    game.actions.push(action);
+    if action == GameAction::Insurance {
+        game.insurance = insurance_stake(game.hands[0].bet);
     }
-    
-    // Banker draw rules
-    let banker_draws = if p_third.is_none() {
-        // Player stood (6 or 7) -> Banker draws on 0-5, stands on 6-7
-        b_score <= 5
+    game.phase = if next_idx < game.player_hands.len() {
+        GamePhase::PlayerTurn
     } else {
-        // Player drew a third card
-        let p_val = p_third.unwrap();
-        match b_score {
-            0..=2 => true, // Always draw
-            3 => p_val != 8,
-            4 => (2..=7).contains(&p_val),
-            5 => (4..=7).contains(&p_val),
-            6 => (6..=7).contains(&p_val),
-            _ => false, // 7 stands
-        }
+        // Every hand is done - only the seed is left to reveal
+        GamePhase::RoundComplete
     };
+}
+
+/// The round as the operator dealt it: two cards per seat in seat order, then
+/// the dealer up-card. A round over at the deal is settled from the seed instead.
+fn dealt_round(game_id: u64, pending: &PendingGame, cards: Vec<Card>, now: u64, rules: &BlackjackRules) -> ActiveGame {
+    let seats = pending.seat_bets.len();
+    assert!(cards.len() == 2 * seats + 1, "Deal two cards per seat and the dealer up-card");
+    let mut cards = cards.into_iter();
+    let player_hands: Vec<Vec<Card>> = (0..seats).map(|_| cards.by_ref().take(2).collect()).collect();
+    let dealer_up_card = cards.next().expect("The dealer up-card is dealt");
+    assert!(
+        !blackjack::settles_at_deal(&player_hands, std::slice::from_ref(&dealer_up_card), rules),
+        "The round is over at the deal: reveal the seed to settle it"
+    );
     
-    if banker_draws {
-        let c = deck.pop().unwrap();
-        banker_hand.push(c);
-        b_score = calculate_baccarat_score(&banker_hand);
+    let hands = seat_hands(&player_hands, &pending.seat_bets);
+    let side_bets = settle_side_bets(&pending.side_bets, &player_hands[0], &dealer_up_card);
+    ActiveGame {
+        game_id,
+        commitment: pending.commitment.clone(),
+        bet: pending.bet,
+        game_type: GameType::Blackjack,
+        started_at: now,
+        phase: GamePhase::PlayerTurn,
+        active_hand_index: next_open_hand(&hands) as u32,
+        player_hands,
+        hands,
+        dealer_hand: vec![dealer_up_card],
+        actions: Vec::new(),
+        insurance: 0,
+        side_bets,
     }
-    
-    let winner = if p_score > b_score {
-        BaccaratBetType::Player
-    } else if b_score > p_score {
-        BaccaratBetType::Banker
-    } else {
-        BaccaratBetType::Tie
-    };
-    
-    (winner, player_hand, banker_hand, p_score, b_score)
 }
@@ -0,0 +1,90 @@
+//! Commit-reveal helpers shared by the Bank, the player chains and off-chain auditors.
+//!
+//! Server seeds are secrets held by the Bank's operator off-chain: 32 random
+//! bytes, hex-encoded. The operator adds `seed_commitment(server_seed)` to the
+//! Bank's pool with `Operation::CommitSeeds` ahead of time. A player chain
+//! reserves one with `Operation::RequestSeedCommitment` before each game and
+//! picks its client seed once it has arrived. The seed itself only reaches chain
+//! state with `Operation::RevealSeed` once the game is over, and the Bank
+//! checks it against the commitment and every card already shown before it
+//! settles. Until then nobody but the operator can work out the shuffle.
+//!
+//! The cards are dealt from `game_seed(server_seed, client_seed)`, so neither
+//! side picks the outcome alone.
+
+use async_graphql::SimpleObject;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Domain separator prefixed to the `game_seed` preimage
+pub const GAME_SEED_DOMAIN_V2: &[u8] = b"linerabet/game-seed/v2";
+
+/// Domain separator prefixed to the `seed_commitment` preimage
+pub const SEED_COMMITMENT_DOMAIN_V2: &[u8] = b"linerabet/seed-commitment/v2";
+
+/// Whether `value` is 32 bytes as lowercase hex, the shape of server seeds and
+/// of their commitments
+pub fn is_hex_32_bytes(value: &str) -> bool {
+    value.len() == 64 && value.bytes().all(|byte| matches!(byte, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// Hex-encoded SHA-256 of `SEED_COMMITMENT_DOMAIN_V2` followed by the server
+/// seed's ASCII bytes
+pub fn seed_commitment(server_seed: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(SEED_COMMITMENT_DOMAIN_V2);
+    hasher.update(server_seed.as_bytes());
+    to_hex(&hasher.finalize())
+}
+
+fn first_u64(digest: &[u8]) -> u64 {
+    let mut first = [0u8; 8];
+    first.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(first)
+}
+
+/// Player-chosen entropy sent with a game request
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct ClientSeed {
+    pub seed: String,
+    pub nonce: u64,
+}
+
+impl ClientSeed {
+    /// Client seed from optional operation arguments (no seed means none; the nonce defaults to 0)
+    pub fn from_parts(seed: Option<String>, nonce: Option<u64>) -> Option<ClientSeed> {
+        seed.map(|seed| ClientSeed { seed, nonce: nonce.unwrap_or(0) })
+    }
+}
+
+/// Seed the game is actually played with: the first 8 bytes (big-endian
+/// `u64`) of SHA-256 over
+/// - the ASCII bytes of `GAME_SEED_DOMAIN_V2`
+/// - the server seed's ASCII bytes
+/// - a `1` byte and the client seed's UTF-8 length as a big-endian `u32`,
+///   followed by its bytes and the nonce as a big-endian `u64` (a single `0`
+///   byte without a client seed)
+pub fn game_seed(server_seed: &str, client_seed: Option<&ClientSeed>) -> u64 {
+    let mut hasher = Sha256::new();
+    hasher.update(GAME_SEED_DOMAIN_V2);
+    hasher.update(server_seed.as_bytes());
+    match client_seed {
+        Some(client) => {
+            hasher.update([1u8]);
+            hasher.update((client.seed.len() as u32).to_be_bytes());
+            hasher.update(client.seed.as_bytes());
+            hasher.update(client.nonce.to_be_bytes());
+        }
+        None => hasher.update([0u8]),
+    }
+    first_u64(&hasher.finalize())
+}
+
+/// Checks a revealed server seed against the commitment its game was bound to.
+pub fn verify_seed_commitment(commitment: &str, server_seed: &str) -> bool {
+    seed_commitment(server_seed) == commitment
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,341 @@
+//! Baccarat punto banco engine: shoe shuffle, third-card tableau, payouts.
+
+use crate::games::shoe::Shoe;
+use crate::rng::RngVersion;
+use crate::{
+    BaccaratBetType, BaccaratSideBet, BaccaratSideBetResult, BaccaratSideBetType, BaccaratVariant, BaccaratWager,
+    BaccaratWagerResult, Card,
+};
+
+/// Payouts are worked out in hundredths of a chip, where the 5% commission is exact
+pub const BACCARAT_PAYOUT_SCALE: u64 = 100;
+
+/// Table limit on a single main bet, which keeps every stake, payout and
+/// liability sum far inside a u64
+pub const MAX_BACCARAT_WAGER: u64 = 1_000_000;
+
+/// Baccarat point value of a single card (10s and faces count 0)
+fn card_points(card: &Card) -> u8 {
+    match card.value.as_str() {
+        "ace" => 1,
+        "2" => 2,
+        "3" => 3,
+        "4" => 4,
+        "5" => 5,
+        "6" => 6,
+        "7" => 7,
+        "8" => 8,
+        "9" => 9,
+        _ => 0, // 10, J, Q, K are 0
+    }
+}
+
+pub fn calculate_baccarat_score(cards: &[Card]) -> u8 {
+    cards.iter().fold(0, |score, c| (score + card_points(c)) % 10)
+}
+
+/// Play one coup from the cards left in `shoe`:
+/// (winner, player hand, banker hand, player score, banker score)
+pub fn run_baccarat_game(shoe: &Shoe, seed: u64, rng_version: RngVersion) -> (BaccaratBetType, Vec<Card>, Vec<Card>, u8, u8) {
+    let mut deck = shoe.shuffled(seed, rng_version);
+    
+    // Draw initial cards
+    let p1 = deck.pop().unwrap();
+    let b1 = deck.pop().unwrap();
+    let p2 = deck.pop().unwrap();
+    let b2 = deck.pop().unwrap();
+    
+    let mut player_hand = vec![p1, p2];
+    let mut banker_hand = vec![b1, b2];
+    
+    let mut p_score = calculate_baccarat_score(&player_hand);
+    let mut b_score = calculate_baccarat_score(&banker_hand);
+    
+    // Natural win check (8 or 9)
+    if p_score >= 8 || b_score >= 8 {
+        let winner = baccarat_winner(p_score, b_score);
+        return (winner, player_hand, banker_hand, p_score, b_score);
+    }
+    
+    // Player draw rules
+    let mut p_third = None;
+    if p_score <= 5 {
+        let c = deck.pop().unwrap();
+        
+        // Third card value for Banker rule
+        p_third = Some(card_points(&c));
+        
+        player_hand.push(c);
+        p_score = calculate_baccarat_score(&player_hand);
+    }
+    
+    // Banker draw rules
+    let banker_draws = match p_third {
+        // Player stood (6 or 7) -> Banker draws on 0-5, stands on 6-7
+        None => b_score <= 5,
+        // Player drew a third card
+        Some(p_val) => match b_score {
+            0..=2 => true, // Always draw
+            3 => p_val != 8,
+            4 => (2..=7).contains(&p_val),
+            5 => (4..=7).contains(&p_val),
+            6 => (6..=7).contains(&p_val),
+            _ => false, // 7 stands
+        },
+    };
+    
+    if banker_draws {
+        let c = deck.pop().unwrap();
+        banker_hand.push(c);
+        b_score = calculate_baccarat_score(&banker_hand);
+    }
+    
+    let winner = baccarat_winner(p_score, b_score);
+    
+    (winner, player_hand, banker_hand, p_score, b_score)
+}
+
+pub fn baccarat_winner(p_score: u8, b_score: u8) -> BaccaratBetType {
+    match p_score.cmp(&b_score) {
+        std::cmp::Ordering::Greater => BaccaratBetType::Player,
+        std::cmp::Ordering::Less => BaccaratBetType::Banker,
+        std::cmp::Ordering::Equal => BaccaratBetType::Tie,
+    }
+}
+
+/// Total returned to the player (stake included) for `amount` on `bet_type`,
+/// in hundredths of a chip
+pub fn baccarat_payout_hundredths(
+    variant: BaccaratVariant,
+    bet_type: BaccaratBetType,
+    winner: BaccaratBetType,
+    banker_hand: &[Card],
+    amount: u64,
+) -> u64 {
+    let chips = amount * BACCARAT_PAYOUT_SCALE;
+    if winner == bet_type {
+        match bet_type {
+            BaccaratBetType::Tie => chips * 9, // 8:1 payout = 9x total return
+            BaccaratBetType::Player => chips * 2, // 1:1 payout
+            BaccaratBetType::Banker => {
+                let banker_score = calculate_baccarat_score(banker_hand);
+                match variant {
+                    BaccaratVariant::Classic => chips + chips * 95 / 100, // 0.95:1 payout (5% commission)
+                    BaccaratVariant::SuperSix if banker_score == 6 => chips + chips / 2, // 1:2 payout
+                    BaccaratVariant::Ez if banker_score == 7 && banker_hand.len() == 3 => chips, // Dragon 7 pushes
+                    BaccaratVariant::SuperSix | BaccaratVariant::Ez => chips * 2, // 1:1 payout
+                }
+            }
+        }
+    } else if winner == BaccaratBetType::Tie {
+        // Push on Tie
+        chips
+    } else {
+        0
+    }
+}
+
+/// Total returned to the player (stake included) in whole chips. A fraction
+/// of a chip is dropped here; the Bank carries it with `settle_baccarat_wagers`.
+pub fn calculate_baccarat_payout(
+    variant: BaccaratVariant,
+    bet_type: BaccaratBetType,
+    winner: BaccaratBetType,
+    banker_hand: &[Card],
+    amount: u64,
+) -> u64 {
+    baccarat_payout_hundredths(variant, bet_type, winner, banker_hand, amount) / BACCARAT_PAYOUT_SCALE
+}
+
+/// Worst-case net house loss for a baccarat bet (a winning bet, net of the
+/// stake), counting a Classic commission credit rounding it up
+pub fn baccarat_max_liability(variant: BaccaratVariant, amount: u64, bet_type: BaccaratBetType) -> u64 {
+    match bet_type {
+        BaccaratBetType::Player => amount,
+        BaccaratBetType::Banker => match variant {
+            BaccaratVariant::Classic => amount.checked_mul(95).expect("Baccarat liability overflows").div_ceil(100),
+            BaccaratVariant::SuperSix | BaccaratVariant::Ez => amount,
+        },
+        BaccaratBetType::Tie => amount.checked_mul(8).expect("Baccarat liability overflows"),
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Several main bets on one coup
+// ────────────────────────────────────────────────────────────────────────────
+
+/// Check main bets: at least one, each bet type at most once, none empty
+/// and none above the table limit
+pub fn validate_baccarat_wagers(wagers: &[BaccaratWager]) -> Result<(), String> {
+    if wagers.is_empty() {
+        return Err("Place at least one bet".to_string());
+    }
+    for (idx, wager) in wagers.iter().enumerate() {
+        if wager.amount == 0 {
+            return Err(format!("{:?} bet must be positive", wager.bet_type));
+        }
+        if wager.amount > MAX_BACCARAT_WAGER {
+            return Err(format!("{:?} bet is over the table limit of {}", wager.bet_type, MAX_BACCARAT_WAGER));
+        }
+        if wagers[..idx].iter().any(|other| other.bet_type == wager.bet_type) {
+            return Err(format!("{:?} is bet twice", wager.bet_type));
+        }
+    }
+    Ok(())
+}
+
+/// Settle every main bet against the coup.
+///
+/// `credit` is the hundredths of a chip the player is still owed from earlier
+/// commissions. A payout with a fraction of a chip takes the credit, pays the
+/// whole chips and leaves the rest as the returned credit, so over a session
+/// the commission taken is exactly 5%.
+pub fn settle_baccarat_wagers(
+    variant: BaccaratVariant,
+    wagers: &[BaccaratWager],
+    winner: BaccaratBetType,
+    banker_hand: &[Card],
+    credit: u64,
+) -> (Vec<BaccaratWagerResult>, u64) {
+    let mut credit = credit;
+    let results = wagers.iter()
+        .map(|wager| {
+            let mut payout = baccarat_payout_hundredths(variant, wager.bet_type, winner, banker_hand, wager.amount);
+            if payout % BACCARAT_PAYOUT_SCALE != 0 {
+                payout += credit;
+                credit = payout % BACCARAT_PAYOUT_SCALE;
+            }
+            BaccaratWagerResult {
+                bet_type: wager.bet_type,
+                amount: wager.amount,
+                payout: payout / BACCARAT_PAYOUT_SCALE,
+            }
+        })
+        .collect();
+    (results, credit)
+}
+
+/// Total staked on the main bets, or an error if it does not fit in a u64
+pub fn baccarat_wagers_total(wagers: &[BaccaratWager]) -> Result<u64, String> {
+    checked_total(wagers.iter().map(|wager| wager.amount))
+}
+
+/// Total staked on the side bets, or an error if it does not fit in a u64
+pub fn baccarat_side_bets_total(side_bets: &[BaccaratSideBet]) -> Result<u64, String> {
+    checked_total(side_bets.iter().map(|side_bet| side_bet.amount))
+}
+
+fn checked_total(mut amounts: impl Iterator<Item = u64>) -> Result<u64, String> {
+    amounts
+        .try_fold(0u64, |total, amount| total.checked_add(amount))
+        .ok_or_else(|| "Stakes overflow".to_string())
+}
+
+/// Worst-case net house loss over the three possible winners. Bets on
+/// opposite sides hedge each other, so this is below the sum of their limits.
+pub fn baccarat_wagers_max_liability(variant: BaccaratVariant, wagers: &[BaccaratWager]) -> u64 {
+    let staked = baccarat_wagers_total(wagers).expect("Baccarat liability overflows");
+    [BaccaratBetType::Player, BaccaratBetType::Banker, BaccaratBetType::Tie]
+        .into_iter()
+        .map(|winner| {
+            let paid = checked_total(wagers.iter().map(|wager| match wager.bet_type {
+                bet_type if bet_type == winner => wager.amount
+                    .checked_add(baccarat_max_liability(variant, wager.amount, bet_type))
+                    .expect("Baccarat liability overflows"),
+                _ if winner == BaccaratBetType::Tie => wager.amount,
+                _ => 0,
+            }))
+            .expect("Baccarat liability overflows");
+            paid.saturating_sub(staked)
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Side bets, settled on the coup's cards
+// ────────────────────────────────────────────────────────────────────────────
+
+/// Check side bets against the main bets: each type at most once, staking
+/// between 1 and `amount`, the total on the main bets
+pub fn validate_baccarat_side_bets(side_bets: &[BaccaratSideBet], amount: u64) -> Result<(), String> {
+    for (idx, side_bet) in side_bets.iter().enumerate() {
+        if side_bet.amount == 0 || side_bet.amount > amount {
+            return Err(format!("{:?} must stake between 1 and the main bet", side_bet.bet_type));
+        }
+        if side_bets[..idx].iter().any(|other| other.bet_type == side_bet.bet_type) {
+            return Err(format!("{:?} is staked twice", side_bet.bet_type));
+        }
+    }
+    Ok(())
+}
+
+/// Settle every side bet on the final hands
+pub fn settle_baccarat_side_bets(
+    side_bets: &[BaccaratSideBet],
+    player_hand: &[Card],
+    banker_hand: &[Card],
+) -> Vec<BaccaratSideBetResult> {
+    side_bets.iter()
+        .map(|side_bet| BaccaratSideBetResult {
+            bet_type: side_bet.bet_type,
+            amount: side_bet.amount,
+            payout: baccarat_side_bet_payout(side_bet.bet_type, side_bet.amount, player_hand, banker_hand),
+        })
+        .collect()
+}
+
+/// Total returned to the player (stake included) for `amount` on a side bet
+pub fn baccarat_side_bet_payout(bet_type: BaccaratSideBetType, amount: u64, player_hand: &[Card], banker_hand: &[Card]) -> u64 {
+    let pair = |hand: &[Card]| hand[0].value == hand[1].value;
+    let perfect_pair = |hand: &[Card]| hand[0].id == hand[1].id;
+    let odds = match bet_type {
+        BaccaratSideBetType::PlayerPair => pair(player_hand).then_some(11),
+        BaccaratSideBetType::BankerPair => pair(banker_hand).then_some(11),
+        BaccaratSideBetType::EitherPair => (pair(player_hand) || pair(banker_hand)).then_some(5),
+        BaccaratSideBetType::PerfectPair => (perfect_pair(player_hand) || perfect_pair(banker_hand)).then_some(25),
+        BaccaratSideBetType::DragonPlayer => return dragon_bonus_payout(amount, player_hand, banker_hand),
+        BaccaratSideBetType::DragonBanker => return dragon_bonus_payout(amount, banker_hand, player_hand),
+    };
+    odds.map_or(0, |odds| amount + amount * odds)
+}
+
+/// Dragon Bonus on `side`: a natural win pays 1:1 and a natural tie pushes;
+/// otherwise a win by 4 to 9 points pays 1:1 to 30:1
+fn dragon_bonus_payout(amount: u64, side: &[Card], other: &[Card]) -> u64 {
+    let score = calculate_baccarat_score(side);
+    let other_score = calculate_baccarat_score(other);
+    let natural = side.len() == 2 && score >= 8;
+    if natural && score > other_score {
+        return amount * 2;
+    }
+    if natural && score == other_score {
+        return amount;
+    }
+    let odds = match score.saturating_sub(other_score) {
+        9 => 30,
+        8 => 10,
+        7 => 6,
+        6 => 4,
+        5 => 2,
+        4 => 1,
+        _ => return 0,
+    };
+    amount + amount * odds
+}
+
+/// Worst-case net house loss on the side bets
+pub fn baccarat_side_bets_max_liability(side_bets: &[BaccaratSideBet]) -> u64 {
+    side_bets.iter()
+        .map(|side_bet| {
+            let odds = match side_bet.bet_type {
+                BaccaratSideBetType::PlayerPair | BaccaratSideBetType::BankerPair => 11,
+                BaccaratSideBetType::EitherPair => 5,
+                BaccaratSideBetType::PerfectPair => 25,
+                BaccaratSideBetType::DragonPlayer | BaccaratSideBetType::DragonBanker => 30,
+            };
+            side_bet.amount.checked_mul(odds).expect("Baccarat liability overflows")
+        })
+        .try_fold(0u64, |total, liability| total.checked_add(liability))
+        .expect("Baccarat liability overflows")
+}
@@ -0,0 +1,736 @@
+//! Blackjack round engine.
+//!
+//! The Bank's operator deals rounds off-chain with `BlackjackTable` and works
+//! out what to reveal with `next_reveal`; the Bank and the player chain apply
+//! revealed cards with `apply_blackjack_action`; the Bank replays the round
+//! from the revealed seed to check and settle it, and the service replays
+//! settled rounds to verify them.
+
+use crate::games::shoe::{RoundDeck, Shoe};
+use crate::rng::RngVersion;
+use crate::{
+    BlackjackPayout, BlackjackRules, Card, GameAction, GameResult, HandState, SeatResult, SideBetKind,
+    SideBetOutcome, SideBetResult, SideBets, SurrenderRule,
+};
+
+/// Blackjack bets on offer. Tens pay 3:2 and 6:5 naturals, insurance and
+/// surrender in whole chips.
+pub const ALLOWED_BETS: [u64; 5] = [10, 20, 30, 40, 50];
+
+/// Most hands a blackjack round can reach through splits
+pub const MAX_BLACKJACK_HANDS: usize = 4;
+
+/// Most seats one player can take in a blackjack round
+pub const MAX_BLACKJACK_SEATS: usize = 5;
+
+/// Blackjack round rebuilt from the seed. Only the operator holding the server
+/// seed can build one before settlement; the chains see just the cards revealed.
+pub struct BlackjackTable {
+    deck: RoundDeck,
+    pub player_hands: Vec<Vec<Card>>,
+    /// Stake and progress of each entry of `player_hands`
+    pub hands: Vec<HandState>,
+    /// Bet each seat was dealt with
+    pub seat_bets: Vec<u64>,
+    pub dealer_hand: Vec<Card>,
+    pub active_idx: usize,
+    /// Insurance stake taken against a dealer ace (0 if declined)
+    pub insurance: u64,
+    /// The player took even money on a natural against a dealer ace
+    pub even_money: bool,
+    /// The player gave up the hand for half the bet
+    pub surrendered: bool,
+    pub rules: BlackjackRules,
+    // The dealer has not checked for a natural yet: the first decision comes
+    // before the peek against an ace (insurance) or, with early surrender, a ten
+    peek_pending: bool,
+}
+
+impl BlackjackTable {
+    /// Shuffle what is left in the shoe with the seed and deal player, player,
+    /// dealer up, dealer hole
+    pub fn deal(shoe: &Shoe, seed: u64, rng_version: RngVersion, bet: u64, rules: BlackjackRules) -> Self {
+        Self::deal_seats(shoe, seed, rng_version, &[bet], rules)
+    }
+    
+    /// Deal one hand per bet from the same shuffle: two cards to each seat in
+    /// turn, then dealer up and dealer hole. With several seats the dealer peeks
+    /// straight away (no insurance or surrender) and seat naturals stand.
+    pub fn deal_seats(shoe: &Shoe, seed: u64, rng_version: RngVersion, bets: &[u64], rules: BlackjackRules) -> Self {
+        assert!(!bets.is_empty(), "A round needs at least one seat");
+        let mut deck = shoe.deal(seed, rng_version);
+        
+        let player_hands: Vec<Vec<Card>> = bets.iter()
+            .map(|_| vec![deck.draw(), deck.draw()])
+            .collect();
+        let dealer_up = deck.draw();
+        let dealer_hole = deck.draw();
+        let peek_pending = bets.len() == 1 && decides_before_peek(&dealer_up, &rules);
+        let hands = seat_hands(&player_hands, bets);
+        let active_idx = next_open_hand(&hands);
+        
+        BlackjackTable {
+            deck,
+            player_hands,
+            hands,
+            seat_bets: bets.to_vec(),
+            dealer_hand: vec![dealer_up, dealer_hole],
+            active_idx,
+            insurance: 0,
+            even_money: false,
+            surrendered: false,
+            rules,
+            peek_pending,
+        }
+    }
+    
+    /// Either side was dealt 21 - the round settles without player decisions
+    /// (without a dealer peek only player naturals end it early, on every seat)
+    pub fn has_natural(&self) -> bool {
+        self.has_player_natural() || (self.rules.dealer_peek && self.has_dealer_natural())
+    }
+    
+    /// 21 on the initial, unsplit hand of every seat
+    fn has_player_natural(&self) -> bool {
+        (0..self.hands.len()).all(|idx| is_natural(&self.player_hands[idx], &self.hands[idx]))
+    }
+    
+    fn has_dealer_natural(&self) -> bool {
+        calculate_hand_value(&self.dealer_hand[..2]) == 21
+    }
+    
+    /// Insurance (or even money) is on offer: the dealer shows an ace and the
+    /// player has not made a decision yet
+    pub fn insurance_offered(&self) -> bool {
+        self.peek_pending && is_ace(&self.dealer_hand[0])
+    }
+    
+    /// The shoe ran out mid-round and its discards were shuffled back in
+    pub fn reshuffled_mid_round(&self) -> bool {
+        self.deck.reshuffled()
+    }
+    
+    pub fn is_finished(&self) -> bool {
+        (!self.peek_pending && self.has_natural()) || self.active_idx >= self.player_hands.len()
+    }
+    
+    /// Every stake on the table, insurance included
+    pub fn total_bet(&self) -> u64 {
+        self.hands.iter().map(|hand| hand.bet).sum::<u64>() + self.insurance
+    }
+    
+    /// Apply a player action, returning the cards drawn for it.
+    ///
+    /// Against a dealer ace the first action settles insurance: `Insurance` and
+    /// `EvenMoney` draw nothing, any other action declines. Early surrender is
+    /// also taken before the peek. The dealer then peeks, and a natural on either
+    /// side ends the round before a declining action is played.
+    pub fn apply(&mut self, action: GameAction) -> Vec<Card> {
+        if self.peek_pending {
+            self.peek_pending = false;
+            match action {
+                GameAction::Insurance => {
+                    let stake = insurance_stake(self.seat_bets[0]);
+                    assert!(stake > 0, "Bet too small to insure");
+                    assert!(!self.has_player_natural(), "Take even money on a natural");
+                    self.insurance = stake;
+                    return Vec::new();
+                }
+                GameAction::EvenMoney => {
+                    assert!(self.has_player_natural(), "Even money needs a natural");
+                    self.even_money = true;
+                    return Vec::new();
+                }
+                GameAction::Surrender if self.rules.surrender == SurrenderRule::Early => {
+                    self.surrender();
+                    return Vec::new();
+                }
+                _ if self.has_natural() => return Vec::new(),
+                _ => {}
+            }
+        }
+        
+        match action {
+            GameAction::Hit | GameAction::Stand | GameAction::DoubleDown | GameAction::Split => {}
+            GameAction::Surrender => {
+                self.surrender();
+                return Vec::new();
+            }
+            GameAction::Insurance | GameAction::EvenMoney => {
+                panic!("Insurance is only offered as the first decision against a dealer ace")
+            }
+        }
+        
+        let deck = &mut self.deck;
+        let mut drawn = Vec::new();
+        self.active_idx = apply_blackjack_action(&mut self.player_hands, &mut self.hands, self.active_idx, action, &self.rules, || {
+            let card = deck.draw();
+            drawn.push(card.clone());
+            card
+        });
+        drawn
+    }
+    
+    /// Give up the initial hand; half the bet comes back at settlement
+    fn surrender(&mut self) {
+        assert!(self.rules.surrender != SurrenderRule::NotAllowed, "Surrender is not allowed");
+        assert!(self.seat_bets.len() == 1, "Surrender is not offered with several seats");
+        assert!(surrender_offered(self.seat_bets[0]), "Bet cannot be halved to surrender");
+        assert!(
+            self.player_hands.len() == 1 && self.player_hands[0].len() == 2,
+            "Surrender is only allowed as the first decision"
+        );
+        assert!(!self.has_player_natural(), "Cannot surrender a natural");
+        self.surrendered = true;
+        self.hands[0].finished = true;
+        self.active_idx = self.player_hands.len();
+    }
+    
+    /// Draw the dealer's cards once the player is done: only while some hand is
+    /// neither busted, a natural, surrendered nor paid even money. `settle` does
+    /// this too; calling it first shows the whole round before it is paid.
+    pub fn play_dealer(&mut self) {
+        if self.surrendered || self.even_money || self.has_dealer_natural() {
+            return;
+        }
+        let dealer_plays = (0..self.hands.len()).any(|idx| {
+            !is_natural(&self.player_hands[idx], &self.hands[idx]) && calculate_hand_value(&self.player_hands[idx]) <= 21
+        });
+        if dealer_plays {
+            while dealer_draws(&self.dealer_hand, &self.rules) {
+                let card = self.deck.draw();
+                self.dealer_hand.push(card);
+            }
+        }
+    }
+    
+    /// Play out the dealer and pay every hand:
+    /// (result, payout, player hands, hand states with per-hand results, dealer hand).
+    /// The payout is the sum of the hand payouts plus insurance, which pays 2:1
+    /// on a dealer natural and is lost otherwise.
+    pub fn settle(mut self) -> (GameResult, u64, Vec<Vec<Card>>, Vec<HandState>, Vec<Card>) {
+        let player_natural = self.has_player_natural();
+        let dealer_natural = self.has_dealer_natural();
+        let base_bet = self.seat_bets[0];
+        self.play_dealer();
+        
+        if self.surrendered {
+            self.hands[0].result = Some(GameResult::Surrender);
+            self.hands[0].payout = surrender_refund(base_bet);
+        } else if self.even_money {
+            self.hands[0].result = Some(GameResult::PlayerWin);
+            self.hands[0].payout = base_bet * 2;
+        } else {
+            let naturals: Vec<bool> = (0..self.hands.len())
+                .map(|idx| is_natural(&self.player_hands[idx], &self.hands[idx]))
+                .collect();
+            let dealer_value = calculate_hand_value(&self.dealer_hand);
+            for ((cards, hand), natural) in self.player_hands.iter().zip(&mut self.hands).zip(naturals) {
+                let p_val = calculate_hand_value(cards);
+                // Without a peek a dealer natural only shows now, and takes every
+                // stake the player added since the deal
+                let (result, payout) = if dealer_natural {
+                    if natural { (GameResult::Push, hand.bet) } else { (GameResult::DealerWin, 0) }
+                } else if natural {
+                    (GameResult::PlayerBlackjack, hand.bet + self.rules.blackjack_payout.winnings(hand.bet))
+                } else if p_val > 21 {
+                    (GameResult::PlayerBust, 0)
+                } else if dealer_value > 21 {
+                    (GameResult::DealerBust, hand.bet * 2)
+                } else if p_val > dealer_value {
+                    (GameResult::PlayerWin, hand.bet * 2)
+                } else if p_val == dealer_value {
+                    (GameResult::Push, hand.bet)
+                } else {
+                    (GameResult::DealerWin, 0)
+                };
+                hand.result = Some(result);
+                hand.payout = payout;
+            }
+        }
+        for hand in &mut self.hands {
+            hand.finished = true;
+        }
+        
+        let insurance_payout = if dealer_natural { self.insurance * 3 } else { 0 };
+        let total_payout = self.hands.iter().map(|hand| hand.payout).sum::<u64>() + insurance_payout;
+        
+        // Determine summary result for record keeping: a single-seat round that
+        // ended at the deal keeps the opening hand's result
+        let ended_at_deal = self.surrendered || self.even_money || player_natural || dealer_natural;
+        let result = if ended_at_deal && self.seat_bets.len() == 1 {
+            self.hands[0].result.expect("the opening hand is settled")
+        } else {
+            summary_result(total_payout, self.total_bet())
+        };
+
+        (result, total_payout, self.player_hands, self.hands, self.dealer_hand)
+    }
+}
+
+/// Hands of each seat added up, in seat order. A seat keeps its only hand's
+/// result; a split seat is judged on what came back against what it staked.
+pub fn seat_results(hands: &[HandState], seat_bets: &[u64]) -> Vec<SeatResult> {
+    seat_bets.iter().enumerate()
+        .map(|(seat, &bet)| {
+            let seat_hands: Vec<&HandState> = hands.iter().filter(|hand| hand.seat == seat as u32).collect();
+            let staked = seat_hands.iter().map(|hand| hand.bet).sum();
+            let payout = seat_hands.iter().map(|hand| hand.payout).sum();
+            let result = match seat_hands.as_slice() {
+                [hand] => hand.result.unwrap_or(GameResult::Push),
+                _ => summary_result(payout, staked),
+            };
+            SeatResult { seat: seat as u32, bet, staked, result, payout }
+        })
+        .collect()
+}
+
+/// Round result from what came back against what was staked
+fn summary_result(payout: u64, staked: u64) -> GameResult {
+    match payout.cmp(&staked) {
+        std::cmp::Ordering::Greater => GameResult::PlayerWin,
+        std::cmp::Ordering::Less => GameResult::DealerWin,
+        std::cmp::Ordering::Equal => GameResult::Push,
+    }
+}
+
+/// Insurance stake for a bet: half of it, rounded down
+pub fn insurance_stake(bet: u64) -> u64 {
+    bet / 2
+}
+
+/// Chips returned on a surrendered bet: half of it
+pub fn surrender_refund(bet: u64) -> u64 {
+    bet / 2
+}
+
+/// Surrender is only offered on an even bet, so no half chip is rounded away
+pub fn surrender_offered(bet: u64) -> bool {
+    bet > 0 && bet % 2 == 0
+}
+
+/// Worst-case net house loss for a blackjack bet: every split hand doubled and won.
+/// Insurance never adds to it - it only pays when the dealer natural takes the main bet.
+pub fn blackjack_max_liability(bet: u64, rules: &BlackjackRules) -> u64 {
+    // Each hand stakes 2 * bet after doubling and wins the same again
+    bet.saturating_mul(2 * rules.max_split_hands as u64)
+}
+
+/// Check a multi-seat round's bets: one to `max_seats` seats, each an allowed
+/// bet whose natural the house payout pays in whole chips
+pub fn validate_seat_bets(bets: &[u64], allowed: &[u64], rules: &BlackjackRules) -> Result<(), String> {
+    if bets.is_empty() || bets.len() > rules.max_seats as usize {
+        return Err(format!("Take 1 to {} seats", rules.max_seats));
+    }
+    if let Some(bet) = bets.iter().find(|bet| !allowed.contains(bet)) {
+        return Err(format!("Bet {bet} is not allowed"));
+    }
+    if let Some(bet) = bets.iter().find(|&&bet| !rules.blackjack_payout.pays_exactly(bet)) {
+        return Err(format!("Bet {bet} cannot be paid exactly at {:?}", rules.blackjack_payout));
+    }
+    Ok(())
+}
+
+/// Whether the dealer takes another card (below 17, or a soft 17 under H17)
+fn dealer_draws(hand: &[Card], rules: &BlackjackRules) -> bool {
+    let value = calculate_hand_value(hand);
+    value < 17 || (value == 17 && rules.dealer_hits_soft_17 && is_soft(hand))
+}
+
+/// An ace in the hand still counts as 11
+pub fn is_soft(cards: &[Card]) -> bool {
+    let hard: u8 = cards.iter()
+        .map(|card| if is_ace(card) { 1 } else { card_value(card) })
+        .sum();
+    hard != calculate_hand_value(cards)
+}
+
+impl BlackjackPayout {
+    /// Winnings on a natural for `bet`, rounded down
+    pub fn winnings(self, bet: u64) -> u64 {
+        match self {
+            BlackjackPayout::ThreeToTwo => bet * 3 / 2,
+            BlackjackPayout::SixToFive => bet * 6 / 5,
+        }
+    }
+
+    /// Whether a natural on `bet` wins a whole number of chips
+    pub fn pays_exactly(self, bet: u64) -> bool {
+        match self {
+            BlackjackPayout::ThreeToTwo => bet % 2 == 0,
+            BlackjackPayout::SixToFive => bet % 5 == 0,
+        }
+    }
+}
+
+/// Replay game with given seed and actions (actions after the round ended are ignored)
+pub fn replay_and_verify(
+    shoe: &Shoe,
+    seed: u64,
+    rng_version: RngVersion,
+    bet: u64,
+    rules: BlackjackRules,
+    actions: &[GameAction],
+) -> BlackjackTable {
+    replay_seats(shoe, seed, rng_version, &[bet], rules, actions)
+}
+
+/// `replay_and_verify` for a round dealt to one seat per bet
+pub fn replay_seats(
+    shoe: &Shoe,
+    seed: u64,
+    rng_version: RngVersion,
+    bets: &[u64],
+    rules: BlackjackRules,
+    actions: &[GameAction],
+) -> BlackjackTable {
+    let mut table = BlackjackTable::deal_seats(shoe, seed, rng_version, bets, rules);
+    for action in actions {
+        if table.is_finished() { break; }
+        table.apply(*action);
+    }
+    table
+}
+
+/// What the operator owes a blackjack round next
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Reveal {
+    /// Cards for `Operation::RevealCards`
+    Cards(Vec<Card>),
+    /// The round is over: reveal the server seed with `Operation::RevealSeed`
+    Seed,
+}
+
+/// Next reveal of a round dealt from `seed`, whose accepted `actions` have had
+/// their cards shown up to `revealed` (`None` before the deal). `None` while
+/// the round waits on the player.
+pub fn next_reveal(
+    shoe: &Shoe,
+    seed: u64,
+    rng_version: RngVersion,
+    bets: &[u64],
+    rules: BlackjackRules,
+    actions: &[GameAction],
+    revealed: Option<usize>,
+) -> Option<Reveal> {
+    let mut table = BlackjackTable::deal_seats(shoe, seed, rng_version, bets, rules);
+    let Some(revealed) = revealed else {
+        if table.is_finished() {
+            return Some(Reveal::Seed);
+        }
+        let mut cards = table.player_hands.concat();
+        cards.push(table.dealer_hand[0].clone());
+        return Some(Reveal::Cards(cards));
+    };
+    for action in &actions[..revealed] {
+        table.apply(*action);
+    }
+    let cards = table.apply(*actions.get(revealed)?);
+    Some(if table.is_finished() { Reveal::Seed } else { Reveal::Cards(cards) })
+}
+
+/// The player decides before the dealer peeks: insurance against an ace or,
+/// with early surrender, surrender against a ten
+fn decides_before_peek(dealer_up: &Card, rules: &BlackjackRules) -> bool {
+    is_ace(dealer_up) || (rules.surrender == SurrenderRule::Early && card_value(dealer_up) == 10)
+}
+
+/// Whether the round ends at the deal, judged from the player hands and the
+/// dealer cards seen. With the up-card alone this catches the player naturals
+/// the Bank refuses to deal as an open round; with the hole card too it agrees
+/// with `BlackjackTable::is_finished` right after the deal.
+pub fn settles_at_deal(player_hands: &[Vec<Card>], dealer_hand: &[Card], rules: &BlackjackRules) -> bool {
+    if player_hands.len() == 1 && decides_before_peek(&dealer_hand[0], rules) {
+        return false;
+    }
+    let dealer_natural = dealer_hand.len() == 2 && calculate_hand_value(dealer_hand) == 21;
+    player_hands.iter().all(|hand| calculate_hand_value(hand) == 21) || (rules.dealer_peek && dealer_natural)
+}
+
+/// Hand states for freshly dealt seats. With several seats a natural stands
+/// at once; a lone seat keeps its natural open for the even-money decision.
+pub fn seat_hands(player_hands: &[Vec<Card>], bets: &[u64]) -> Vec<HandState> {
+    let several = bets.len() > 1;
+    player_hands.iter().zip(bets).enumerate()
+        .map(|(seat, (cards, &bet))| HandState {
+            bet,
+            seat: seat as u32,
+            finished: several && calculate_hand_value(cards) == 21,
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Hand to play next: seat by seat, and within a seat in the order the hands
+/// were split (`states.len()` once every hand is done)
+pub fn next_open_hand(states: &[HandState]) -> usize {
+    (0..states.len())
+        .filter(|&idx| !states[idx].finished)
+        .min_by_key(|&idx| (states[idx].seat, idx))
+        .unwrap_or(states.len())
+}
+
+/// 21 on the two cards a seat was dealt
+fn is_natural(cards: &[Card], state: &HandState) -> bool {
+    !state.from_split && cards.len() >= 2 && calculate_hand_value(&cards[..2]) == 21
+}
+
+/// Apply one action to the player hands, drawing cards with `draw`.
+/// Shared by the Bank replay (draws from the deck) and the player chain (draws revealed cards).
+/// Returns the index of the hand to play next (`hands.len()` once every hand is done).
+pub fn apply_blackjack_action(
+    hands: &mut Vec<Vec<Card>>,
+    states: &mut Vec<HandState>,
+    active_idx: usize,
+    action: GameAction,
+    rules: &BlackjackRules,
+    mut draw: impl FnMut() -> Card,
+) -> usize {
+    if matches!(action, GameAction::Hit | GameAction::Stand | GameAction::DoubleDown | GameAction::Split) {
+        let state = states.get(active_idx).expect("No hand left to play");
+        assert!(!state.finished, "Hand {active_idx} is already finished");
+        assert!(
+            hand_actions(hands, states, active_idx, rules).contains(&action),
+            "{action:?} is not allowed on hand {active_idx}"
+        );
+    }
+    match action {
+        GameAction::Hit => {
+            hands[active_idx].push(draw());
+            if calculate_hand_value(&hands[active_idx]) > 21 {
+                states[active_idx].finished = true;
+            }
+        }
+        GameAction::Stand => states[active_idx].finished = true,
+        // Side decisions against a dealer ace leave the hands untouched
+        GameAction::Insurance | GameAction::EvenMoney => {}
+        GameAction::Surrender => {
+            for state in states.iter_mut() {
+                state.finished = true;
+            }
+        }
+        GameAction::DoubleDown => {
+            hands[active_idx].push(draw());
+            let state = &mut states[active_idx];
+            state.bet *= 2;
+            state.doubled = true;
+            state.finished = true;
+        }
+        GameAction::Split => {
+            // Split active hand (new hand is added to the end)
+            let card2 = hands[active_idx].pop().expect("Cannot split an empty hand");
+            let aces = is_ace(&card2);
+            hands.push(vec![card2]);
+            states[active_idx].from_split = true;
+            states.push(HandState {
+                bet: states[active_idx].bet,
+                from_split: true,
+                seat: states[active_idx].seat,
+                ..Default::default()
+            });
+            
+            // Draw new cards for BOTH hands
+            hands[active_idx].push(draw());
+            let last = hands.len() - 1;
+            hands[last].push(draw());
+            
+            // Split aces take one card each
+            if rules.split_aces_one_card && aces {
+                states[active_idx].finished = true;
+                states[last].finished = true;
+            }
+        }
+    }
+    // Split hands are appended, so play moves on to the first open hand of the
+    // lowest seat
+    next_open_hand(states)
+}
+
+/// Hit/Stand/Double/Split legality on the active hand. Shared by the player
+/// chain's offer and the engine's own checks so both sides agree.
+fn hand_actions(hands: &[Vec<Card>], states: &[HandState], active_idx: usize, rules: &BlackjackRules) -> Vec<GameAction> {
+    let (Some(hand), Some(state)) = (hands.get(active_idx), states.get(active_idx)) else {
+        return Vec::new();
+    };
+    let mut actions = vec![GameAction::Hit, GameAction::Stand];
+    if hand.len() == 2 {
+        if !state.from_split || rules.double_after_split {
+            actions.push(GameAction::DoubleDown);
+        }
+        let seat_hands = states.iter().filter(|other| other.seat == state.seat).count();
+        if card_value(&hand[0]) == card_value(&hand[1]) && seat_hands < rules.max_split_hands as usize {
+            actions.push(GameAction::Split);
+        }
+    }
+    actions
+}
+
+/// Actions the player may take on the active hand, as offered by the player chain.
+/// `taken` are the actions the Bank has confirmed so far. Insurance, even money
+/// and surrender are only offered when a single seat is played.
+pub fn available_actions(
+    hands: &[Vec<Card>],
+    states: &[HandState],
+    active_idx: usize,
+    dealer_up: &Card,
+    taken: &[GameAction],
+    rules: &BlackjackRules,
+) -> Vec<GameAction> {
+    let (Some(hand), Some(state)) = (hands.get(active_idx), states.get(active_idx)) else {
+        return Vec::new();
+    };
+    let mut actions = hand_actions(hands, states, active_idx, rules);
+    let single_seat = states.iter().all(|other| other.seat == 0);
+    let natural = hands.len() == 1 && calculate_hand_value(hand) == 21;
+    if taken.is_empty() && is_ace(dealer_up) && single_seat {
+        if natural {
+            actions.push(GameAction::EvenMoney);
+        } else if insurance_stake(state.bet) > 0 {
+            actions.push(GameAction::Insurance);
+        }
+    }
+    // Surrender stays open after an insurance decision, but not after any play
+    let initial_hand = hands.len() == 1 && hand.len() == 2
+        && taken.iter().all(|action| *action == GameAction::Insurance);
+    if rules.surrender != SurrenderRule::NotAllowed && initial_hand && !natural && surrender_offered(state.bet) {
+        actions.push(GameAction::Surrender);
+    }
+    actions
+}
+
+/// Blackjack value of a single card, counting an ace as 11
+pub fn card_value(card: &Card) -> u8 {
+    match card.value.as_str() {
+        "ace" => 11,
+        "king" | "queen" | "jack" => 10,
+        value => value.parse().unwrap_or(0),
+    }
+}
+
+fn is_ace(card: &Card) -> bool {
+    card.value == "ace"
+}
+
+pub fn calculate_hand_value(cards: &[Card]) -> u8 {
+    let mut total = 0u8;
+    let mut aces = 0u8;
+
+    for card in cards {
+        match card.value.as_str() {
+            "ace" => {
+                aces += 1;
+                total = total.saturating_add(11);
+            }
+            "king" | "queen" | "jack" => total = total.saturating_add(10),
+            value => {
+                let parsed = value.parse::<u8>().unwrap_or(0);
+                total = total.saturating_add(parsed);
+            }
+        }
+    }
+
+    while total > 21 && aces > 0 {
+        total -= 10;
+        aces -= 1;
+    }
+
+    total
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Side bets, settled on the first two player cards and the dealer up-card
+// ────────────────────────────────────────────────────────────────────────────
+
+/// Fewest decks a shoe needs before side bets are taken. The pay tables are the
+/// usual six-deck ones: with fewer decks perfect pairs and suited trips get too
+/// rare for them (a single deck returns about 53% on Perfect Pairs).
+pub const SIDE_BET_MIN_DECKS: u8 = 6;
+
+/// Settle every staked side bet on the opening cards
+pub fn settle_side_bets(side_bets: &SideBets, player_hand: &[Card], dealer_up: &Card) -> Vec<SideBetResult> {
+    [
+        (SideBetKind::PerfectPairs, side_bets.perfect_pairs),
+        (SideBetKind::TwentyOnePlusThree, side_bets.twenty_one_plus_three),
+    ]
+    .into_iter()
+    .filter(|&(_, stake)| stake > 0)
+    .map(|(kind, stake)| settle_side_bet(kind, stake, player_hand, dealer_up))
+    .collect()
+}
+
+pub fn settle_side_bet(kind: SideBetKind, stake: u64, player_hand: &[Card], dealer_up: &Card) -> SideBetResult {
+    let outcome = match kind {
+        SideBetKind::PerfectPairs => perfect_pairs(&player_hand[0], &player_hand[1]),
+        SideBetKind::TwentyOnePlusThree => twenty_one_plus_three(&player_hand[0], &player_hand[1], dealer_up),
+    };
+    let payout = outcome.map_or(0, |outcome| stake + stake * side_bet_odds(outcome));
+    SideBetResult { kind, stake, outcome, payout }
+}
+
+/// Worst-case net house loss on the side bets
+pub fn side_bets_max_liability(side_bets: &SideBets) -> u64 {
+    side_bets.perfect_pairs
+        .checked_mul(side_bet_odds(SideBetOutcome::PerfectPair))
+        .zip(side_bets.twenty_one_plus_three.checked_mul(side_bet_odds(SideBetOutcome::SuitedTrips)))
+        .and_then(|(pairs, poker)| pairs.checked_add(poker))
+        .expect("Side bet liability overflows")
+}
+
+/// Winnings per chip staked (to-one odds)
+pub fn side_bet_odds(outcome: SideBetOutcome) -> u64 {
+    match outcome {
+        SideBetOutcome::MixedPair => 6,
+        SideBetOutcome::ColouredPair => 12,
+        SideBetOutcome::PerfectPair => 25,
+        SideBetOutcome::Flush => 5,
+        SideBetOutcome::Straight => 10,
+        SideBetOutcome::ThreeOfAKind => 30,
+        SideBetOutcome::StraightFlush => 40,
+        SideBetOutcome::SuitedTrips => 100,
+    }
+}
+
+pub fn perfect_pairs(first: &Card, second: &Card) -> Option<SideBetOutcome> {
+    if first.value != second.value {
+        None
+    } else if first.suit == second.suit {
+        Some(SideBetOutcome::PerfectPair)
+    } else if is_red(first) == is_red(second) {
+        Some(SideBetOutcome::ColouredPair)
+    } else {
+        Some(SideBetOutcome::MixedPair)
+    }
+}
+
+pub fn twenty_one_plus_three(first: &Card, second: &Card, dealer_up: &Card) -> Option<SideBetOutcome> {
+    let cards = [first, second, dealer_up];
+    let flush = cards.iter().all(|card| card.suit == first.suit);
+    let trips = cards.iter().all(|card| card.value == first.value);
+    let mut ranks = cards.map(rank);
+    ranks.sort_unstable();
+    // Ace plays high (Q-K-A) or low (A-2-3)
+    let straight = (ranks[1] == ranks[0] + 1 && ranks[2] == ranks[1] + 1) || ranks == [2, 3, 14];
+    match (trips, straight, flush) {
+        (true, _, true) => Some(SideBetOutcome::SuitedTrips),
+        (_, true, true) => Some(SideBetOutcome::StraightFlush),
+        (true, _, _) => Some(SideBetOutcome::ThreeOfAKind),
+        (_, true, _) => Some(SideBetOutcome::Straight),
+        (_, _, true) => Some(SideBetOutcome::Flush),
+        _ => None,
+    }
+}
+
+/// Poker rank, 2 to 14 (ace high)
+fn rank(card: &Card) -> u8 {
+    match card.value.as_str() {
+        "jack" => 11,
+        "queen" => 12,
+        "king" => 13,
+        "ace" => 14,
+        value => value.parse().unwrap_or(0),
+    }
+}
+
+fn is_red(card: &Card) -> bool {
+    matches!(card.suit.as_str(), "hearts" | "diamonds")
+}
@@ -0,0 +1,36 @@
+//! Pure game rules shared by the contract, the service and off-chain tooling.
+
+pub mod baccarat;
+pub mod blackjack;
+pub mod roulette;
+pub mod shoe;
+
+use crate::rng::{self, RngVersion};
+use crate::Card;
+
+pub const SUITS: [&str; 4] = ["clubs", "diamonds", "hearts", "spades"];
+pub const VALUES: [&str; 13] = [
+    "2", "3", "4", "5", "6", "7", "8", "9", "10", "jack", "queen", "king", "ace",
+];
+
+/// One ordered 52-card deck
+pub fn create_deck() -> Vec<Card> {
+    let mut deck = Vec::with_capacity(52);
+    for suit in SUITS {
+        for value in VALUES {
+            deck.push(Card::new(suit, value));
+        }
+    }
+    deck
+}
+
+/// `decks` ordered 52-card decks, one after the other
+pub fn create_decks(decks: u8) -> Vec<Card> {
+    (0..decks).flat_map(|_| create_deck()).collect()
+}
+
+/// Shuffle `deck` with the generator `rng_version` selects for `seed`.
+/// Cards are dealt with `deck.pop()`, i.e. from the end.
+pub fn shuffle(deck: &mut [Card], seed: u64, rng_version: RngVersion) {
+    rng::shuffle(deck, rng_version.rng(seed).as_mut());
+}
@@ -0,0 +1,215 @@
+//! Roulette table geometry, wheel spin and payouts.
+//!
+//! Numbers 1-36 sit in 12 rows of three (`1 2 3`, `4 5 6`, ...) with 0 above
+//! the first row. Inside bets must cover a shape that actually exists on that
+//! layout, otherwise `calculate_roulette_payout` would pay e.g. 17:1 on a
+//! "split" listing every number.
+
+use std::fmt;
+
+use crate::rng::RngVersion;
+use crate::{RouletteBet, RouletteBetType};
+
+/// Highest number on a single-zero wheel.
+pub const MAX_NUMBER: u8 = 36;
+
+/// Why a roulette bet was rejected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RouletteBetError {
+    /// The bet list is empty.
+    NoBets,
+    /// A bet stakes zero chips.
+    ZeroAmount,
+    /// A straight-up bet without `number`, or an inside bet without `numbers`.
+    MissingNumbers(RouletteBetType),
+    /// A number outside 0-36.
+    NumberOutOfRange(u8),
+    /// `numbers` does not form the shape required by the bet type.
+    InvalidShape(RouletteBetType),
+}
+
+impl fmt::Display for RouletteBetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RouletteBetError::NoBets => write!(f, "no bets placed"),
+            RouletteBetError::ZeroAmount => write!(f, "bet amount must be non-zero"),
+            RouletteBetError::MissingNumbers(bet_type) => {
+                write!(f, "{:?} bet is missing its numbers", bet_type)
+            }
+            RouletteBetError::NumberOutOfRange(number) => {
+                write!(f, "number {} is not on the table (0-{})", number, MAX_NUMBER)
+            }
+            RouletteBetError::InvalidShape(bet_type) => {
+                write!(f, "numbers do not form a valid {:?} on the table", bet_type)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RouletteBetError {}
+
+/// Validate every bet of a spin; an empty list is rejected.
+pub fn validate_bets(bets: &[RouletteBet]) -> Result<(), RouletteBetError> {
+    if bets.is_empty() {
+        return Err(RouletteBetError::NoBets);
+    }
+    bets.iter().try_for_each(validate_bet)
+}
+
+/// Validate a single bet against the table layout.
+pub fn validate_bet(bet: &RouletteBet) -> Result<(), RouletteBetError> {
+    if bet.amount == 0 {
+        return Err(RouletteBetError::ZeroAmount);
+    }
+
+    let (expected_len, is_valid): (usize, fn(&[u8]) -> bool) = match bet.bet_type {
+        RouletteBetType::Number => {
+            let number = bet.number.ok_or(RouletteBetError::MissingNumbers(bet.bet_type))?;
+            return check_range(number);
+        }
+        RouletteBetType::Split => (2, is_split),
+        RouletteBetType::Street => (3, is_street),
+        RouletteBetType::Corner => (4, is_corner),
+        RouletteBetType::Line => (6, is_line),
+        // Outside bets and the basket cover a fixed set of numbers
+        _ => return Ok(()),
+    };
+
+    let numbers = bet.numbers.as_ref().ok_or(RouletteBetError::MissingNumbers(bet.bet_type))?;
+    numbers.iter().try_for_each(|&number| check_range(number))?;
+
+    let mut sorted = numbers.clone();
+    sorted.sort_unstable();
+    sorted.dedup();
+    if sorted.len() != numbers.len() || sorted.len() != expected_len || !is_valid(&sorted) {
+        return Err(RouletteBetError::InvalidShape(bet.bet_type));
+    }
+    Ok(())
+}
+
+fn check_range(number: u8) -> Result<(), RouletteBetError> {
+    if number > MAX_NUMBER {
+        return Err(RouletteBetError::NumberOutOfRange(number));
+    }
+    Ok(())
+}
+
+/// Winning number (0-36) for a roulette seed
+pub fn spin_roulette(seed: u64, rng_version: RngVersion) -> u8 {
+    rng_version.rng(seed).below(37) as u8
+}
+
+/// Total returned to the player (stakes included) for every winning bet on `outcome`
+pub fn calculate_roulette_payout(bets: &[RouletteBet], outcome: u8) -> u64 {
+    let mut payout = 0;
+    for bet in bets {
+        let win = match bet.bet_type {
+            RouletteBetType::Number => bet.number == Some(outcome),
+            RouletteBetType::Split => {
+                // Check if outcome is in the numbers array
+                bet.numbers.as_ref().is_some_and(|nums| nums.contains(&outcome))
+            },
+            RouletteBetType::Street => {
+                bet.numbers.as_ref().is_some_and(|nums| nums.contains(&outcome))
+            },
+            RouletteBetType::Corner => {
+                bet.numbers.as_ref().is_some_and(|nums| nums.contains(&outcome))
+            },
+            RouletteBetType::Line => {
+                // Line bet: 6 numbers (2 adjacent streets)
+                bet.numbers.as_ref().is_some_and(|nums| nums.contains(&outcome))
+            },
+            RouletteBetType::Basket => {
+                // First Four: 0, 1, 2, 3
+                outcome == 0 || outcome == 1 || outcome == 2 || outcome == 3
+            },
+            RouletteBetType::Red => {
+                let red_numbers = [1, 3, 5, 7, 9, 12, 14, 16, 18, 19, 21, 23, 25, 27, 30, 32, 34, 36];
+                red_numbers.contains(&outcome)
+            },
+            RouletteBetType::Black => {
+                let black_numbers = [2, 4, 6, 8, 10, 11, 13, 15, 17, 20, 22, 24, 26, 28, 29, 31, 33, 35];
+                black_numbers.contains(&outcome)
+            },
+            RouletteBetType::Even => outcome != 0 && outcome % 2 == 0,
+            RouletteBetType::Odd => outcome != 0 && outcome % 2 != 0,
+            RouletteBetType::Low => (1..=18).contains(&outcome),
+            RouletteBetType::High => (19..=36).contains(&outcome),
+            RouletteBetType::Dozen1 => (1..=12).contains(&outcome),
+            RouletteBetType::Dozen2 => (13..=24).contains(&outcome),
+            RouletteBetType::Dozen3 => (25..=36).contains(&outcome),
+            RouletteBetType::Column1 => outcome != 0 && outcome % 3 == 0,  // 3,6,9,12,15,18,21,24,27,30,33,36
+            RouletteBetType::Column2 => outcome != 0 && outcome % 3 == 2,  // 2,5,8,11,14,17,20,23,26,29,32,35
+            RouletteBetType::Column3 => outcome != 0 && outcome % 3 == 1,  // 1,4,7,10,13,16,19,22,25,28,31,34
+        };
+        
+        if win {
+            let multiplier = match bet.bet_type {
+                RouletteBetType::Number => 36,   // 35:1
+                RouletteBetType::Split => 18,    // 17:1
+                RouletteBetType::Street => 12,   // 11:1
+                RouletteBetType::Corner => 9,    // 8:1
+                RouletteBetType::Line => 6,      // 5:1
+                RouletteBetType::Basket => 7,    // 6:1
+                RouletteBetType::Dozen1 | RouletteBetType::Dozen2 | RouletteBetType::Dozen3 => 3, // 2:1
+                RouletteBetType::Column1 | RouletteBetType::Column2 | RouletteBetType::Column3 => 3, // 2:1
+                RouletteBetType::Red | RouletteBetType::Black | 
+                RouletteBetType::Even | RouletteBetType::Odd | 
+                RouletteBetType::Low | RouletteBetType::High => 2, // 1:1
+            };
+            payout += bet.amount * multiplier;
+        }
+    }
+    payout
+}
+
+/// Worst-case net house loss for a spin: the best number for the player, minus the stake
+pub fn roulette_max_liability(bets: &[RouletteBet]) -> u64 {
+    let total_bet: u64 = bets.iter().map(|b| b.amount).sum();
+    (0..=MAX_NUMBER)
+        .map(|outcome| calculate_roulette_payout(bets, outcome))
+        .max()
+        .unwrap_or(0)
+        .saturating_sub(total_bet)
+}
+
+/// Column of a non-zero number: 0 for 1, 4, 7...; 2 for 3, 6, 9...
+fn column(number: u8) -> u8 {
+    (number - 1) % 3
+}
+
+/// Two neighbours in a row or a column, or 0 with 1, 2 or 3.
+fn is_split(n: &[u8]) -> bool {
+    match *n {
+        [0, b] => (1..=3).contains(&b),
+        [a, b] => b - a == 3 || (b - a == 1 && column(a) != 2),
+        _ => false,
+    }
+}
+
+/// A full row, or one of the zero trios 0-1-2 and 0-2-3.
+fn is_street(n: &[u8]) -> bool {
+    match *n {
+        [0, 1, 2] | [0, 2, 3] => true,
+        [a, b, c] => a != 0 && column(a) == 0 && b == a + 1 && c == a + 2,
+        _ => false,
+    }
+}
+
+/// A 2x2 square of numbers.
+fn is_corner(n: &[u8]) -> bool {
+    match *n {
+        [a, b, c, d] => a != 0 && column(a) != 2 && b == a + 1 && c == a + 3 && d == a + 4,
+        _ => false,
+    }
+}
+
+/// Two adjacent rows.
+fn is_line(n: &[u8]) -> bool {
+    match *n {
+        [first, ..] if first != 0 && column(first) == 0 => {
+            n.iter().zip(first..).all(|(&number, expected)| number == expected)
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,166 @@
+//! Multi-deck shoe shared by consecutive card rounds.
+//!
+//! The Bank keeps the shoes of each card game on tables and tracks which cards
+//! are left in each. A round takes a free table, so rounds open at the same time
+//! never deal from the same cards. Each round shuffles the remaining cards with
+//! its own committed game seed, so rounds stay individually verifiable while the
+//! composition carries over from round to round like a real shoe. Once the cut
+//! card comes out the shoe is rebuilt from full decks before the next round.
+
+use async_graphql::SimpleObject;
+use serde::{Deserialize, Serialize};
+
+use crate::games::{create_deck, shuffle};
+use crate::rng::RngVersion;
+use crate::Card;
+
+/// Cards kept in the shoe for each seat of a round, and for the dealer
+pub const MIN_CARDS_PER_SEAT: usize = 10;
+
+/// Fewest cards a round with `seats` seats may start with, whatever the penetration
+pub fn min_round_cards(seats: usize) -> usize {
+    MIN_CARDS_PER_SEAT * (seats + 1)
+}
+
+/// Cards left in a shoe. A round records the shoe it was dealt from, which is
+/// all a replay needs besides the seed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct Shoe {
+    pub decks: u8,
+    /// Reshuffles since the shoe was first built
+    pub shuffles: u64,
+    /// Copies left of each card, in `create_deck` order
+    pub remaining: Vec<u8>,
+}
+
+impl Shoe {
+    /// A full shoe of `decks` decks
+    pub fn new(decks: u8) -> Self {
+        Shoe { decks, shuffles: 0, remaining: vec![decks; 52] }
+    }
+
+    pub fn size(&self) -> usize {
+        self.decks as usize * 52
+    }
+
+    pub fn cards_left(&self) -> usize {
+        self.remaining.iter().map(|&count| count as usize).sum()
+    }
+
+    /// Position of the shoe: cards dealt since the last reshuffle
+    pub fn dealt(&self) -> usize {
+        self.size() - self.cards_left()
+    }
+
+    /// The cut card is out: `penetration_percent` of the shoe is dealt, or too
+    /// few cards are left for a round with `seats` seats
+    pub fn past_cut_card(&self, penetration_percent: u8, seats: usize) -> bool {
+        self.dealt() * 100 >= self.size() * penetration_percent as usize
+            || self.cards_left() < min_round_cards(seats)
+    }
+
+    /// Rebuild the shoe from full decks
+    pub fn reshuffle(&mut self) {
+        self.remaining = vec![self.decks; 52];
+        self.shuffles += 1;
+    }
+
+    /// Remaining cards in a fixed order. A full shoe matches `create_decks`.
+    pub fn cards(&self) -> Vec<Card> {
+        let deck = create_deck();
+        let mut cards = Vec::with_capacity(self.cards_left());
+        for copy in 0..self.decks {
+            for (card, &count) in deck.iter().zip(&self.remaining) {
+                if count > copy {
+                    cards.push(card.clone());
+                }
+            }
+        }
+        cards
+    }
+
+    /// Remaining cards shuffled with the round's seed, dealt with `pop()`
+    pub fn shuffled(&self, seed: u64, rng_version: RngVersion) -> Vec<Card> {
+        let mut cards = self.cards();
+        shuffle(&mut cards, seed, rng_version);
+        cards
+    }
+
+    /// Remaining cards shuffled with the round's seed, to be dealt with `draw()`
+    pub fn deal(&self, seed: u64, rng_version: RngVersion) -> RoundDeck {
+        RoundDeck {
+            cards: self.shuffled(seed, rng_version),
+            dealt: Vec::new(),
+            decks: self.decks,
+            seed,
+            rng_version,
+            reshuffles: 0,
+        }
+    }
+
+    /// Take the cards a round used out of the shoe. A card with no copy left
+    /// was dealt twice, and the shoe is left untouched.
+    pub fn remove(&mut self, cards: &[Card]) -> Result<(), String> {
+        let deck = create_deck();
+        let mut remaining = self.remaining.clone();
+        for card in cards {
+            let index = deck.iter().position(|c| c.id == card.id)
+                .ok_or_else(|| format!("Unknown card {}", card.id))?;
+            remaining[index] = remaining[index]
+                .checked_sub(1)
+                .ok_or_else(|| format!("No {} left in the shoe", card.id))?;
+        }
+        self.remaining = remaining;
+        Ok(())
+    }
+}
+
+/// A round's shuffle of the shoe. Should it run out mid-round, the discards
+/// (every card neither left in it nor dealt this round) are shuffled and dealt
+/// on, as a dealer would rather than stop the round.
+pub struct RoundDeck {
+    cards: Vec<Card>,
+    dealt: Vec<Card>,
+    decks: u8,
+    seed: u64,
+    rng_version: RngVersion,
+    reshuffles: u64,
+}
+
+impl RoundDeck {
+    pub fn draw(&mut self) -> Card {
+        if self.cards.is_empty() {
+            self.reshuffle_discards();
+        }
+        let card = self.cards.pop().expect("a reshuffle always brings cards");
+        self.dealt.push(card.clone());
+        card
+    }
+
+    /// The discards were shuffled back in during the round, so what is left
+    /// of the shoe no longer follows from the cards the round used
+    pub fn reshuffled(&self) -> bool {
+        self.reshuffles > 0
+    }
+
+    fn reshuffle_discards(&mut self) {
+        let mut discards = Shoe::new(self.decks);
+        discards.remove(&self.dealt).expect("every card dealt came from the shoe");
+        if discards.cards_left() == 0 {
+            // Every card of the shoe is on the table: open a fresh pack
+            discards = Shoe::new(self.decks);
+            self.dealt.clear();
+        }
+        self.reshuffles += 1;
+        // Each reshuffle gets its own stream of the round's seed
+        let seed = self.seed ^ self.reshuffles.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        self.cards = discards.shuffled(seed, self.rng_version);
+    }
+}
+
+/// One of a card game's shoes on the Bank and the round dealing from it, if any
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct ShoeTable {
+    pub shoe: Shoe,
+    pub round: Option<u64>,
+}
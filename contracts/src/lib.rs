@@ -5,6 +5,13 @@ use linera_sdk::{
 };
 use serde::{Deserialize, Serialize};
 
+use crate::fairness::ClientSeed;
+use crate::games::shoe::Shoe;
+
+pub mod fairness;
+pub mod games;
+pub mod rng;
+
 pub struct ContractsAbi;
 
 impl ContractAbi for ContractsAbi {
@@ -28,6 +35,184 @@ pub struct CasinoParams {
     /// The Bank chain ID where game verification happens.
     /// All player chains will send messages to this chain.
     pub bank_chain_id: ChainId,
+    /// How long the Bank waits on a player's decision, and a player on the operator
+    #[serde(default)]
+    pub timeouts: GameTimeouts,
+    /// Largest share of the house balance, in basis points, that worst-case
+    /// payouts of open games may reach (10_000 = the whole bankroll)
+    #[serde(default = "default_max_exposure_bps")]
+    pub max_exposure_bps: u64,
+    /// Rate limits for `RequestChips`
+    #[serde(default)]
+    pub faucet: FaucetConfig,
+    /// House rules the Bank deals and replays blackjack with
+    #[serde(default)]
+    pub blackjack_rules: BlackjackRules,
+    /// Shoe sizes and cut-card penetration for the card games
+    #[serde(default)]
+    pub shoe: ShoeConfig,
+    /// How the Bank pays winning Banker bets in baccarat
+    #[serde(default)]
+    pub baccarat_variant: BaccaratVariant,
+}
+
+fn default_max_exposure_bps() -> u64 {
+    5_000
+}
+
+/// Deadlines in microseconds, counted from when the Bank started waiting on
+/// the other side of a game (`waiting_since` of its pending game).
+///
+/// - A blackjack decision is due `blackjack_micros` after the cards were shown.
+///   Past that the operator may reveal the seed anyway: every unfinished hand
+///   stands, then the dealer plays out as usual. The player chain may also
+///   `AbandonGame` once that long has passed since the deal.
+/// - The operator owes the deal, the cards of an accepted action and the seed
+///   of a finished game within `reveal_micros`. Past that the player may
+///   `ClaimUnrevealed` and is paid as if the game had gone their way.
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject)]
+pub struct GameTimeouts {
+    pub blackjack_micros: u64,
+    pub reveal_micros: u64,
+}
+
+impl Default for GameTimeouts {
+    fn default() -> Self {
+        GameTimeouts {
+            blackjack_micros: 10 * 60 * 1_000_000, // 10 minutes
+            reveal_micros: 5 * 60 * 1_000_000,     // 5 minutes
+        }
+    }
+}
+
+impl GameTimeouts {
+    /// Whether a blackjack decision awaited since `since` is overdue at `now`
+    pub fn decision_expired(&self, since: u64, now: u64) -> bool {
+        now >= since.saturating_add(self.blackjack_micros)
+    }
+
+    /// Whether a reveal awaited since `since` is overdue at `now`
+    pub fn reveal_expired(&self, since: u64, now: u64) -> bool {
+        now >= since.saturating_add(self.reveal_micros)
+    }
+}
+
+/// Faucet limits enforced by the Bank for every player (`AccountOwner`).
+///
+/// A player may claim once per `cooldown_micros`, and never more than
+/// `lifetime_cap` chips in total. Claims are paid from the Bank's faucet pool,
+/// not from the house balance.
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject)]
+pub struct FaucetConfig {
+    pub cooldown_micros: u64,
+    pub lifetime_cap: u64,
+}
+
+impl Default for FaucetConfig {
+    fn default() -> Self {
+        FaucetConfig {
+            cooldown_micros: 24 * 60 * 60 * 1_000_000, // 24 hours
+            lifetime_cap: 1_000,
+        }
+    }
+}
+
+/// Blackjack house rules, honoured by the Bank's dealing and replay and by
+/// the actions the player chain offers. Missing fields take the defaults,
+/// which are the rules the casino has always dealt.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, SimpleObject)]
+#[serde(default)]
+pub struct BlackjackRules {
+    /// 52-card decks shuffled together for each round (1 to 8)
+    pub decks: u8,
+    /// H17: the dealer hits a soft 17 (otherwise stands on all 17s)
+    pub dealer_hits_soft_17: bool,
+    pub blackjack_payout: BlackjackPayout,
+    pub double_after_split: bool,
+    /// Most hands a round may reach by splitting, 1 (no splits) to
+    /// `games::blackjack::MAX_BLACKJACK_HANDS`
+    pub max_split_hands: u8,
+    /// Split aces receive one card each and stand
+    pub split_aces_one_card: bool,
+    /// The dealer checks for a natural before the player acts. Without a peek
+    /// a dealer natural is revealed at settlement and takes every stake.
+    pub dealer_peek: bool,
+    pub surrender: SurrenderRule,
+    /// Most seats one player may take in a round, 1 to
+    /// `games::blackjack::MAX_BLACKJACK_SEATS`
+    pub max_seats: u8,
+}
+
+impl Default for BlackjackRules {
+    fn default() -> Self {
+        BlackjackRules {
+            decks: 1,
+            dealer_hits_soft_17: false,
+            blackjack_payout: BlackjackPayout::ThreeToTwo,
+            double_after_split: true,
+            max_split_hands: games::blackjack::MAX_BLACKJACK_HANDS as u8,
+            split_aces_one_card: false,
+            dealer_peek: true,
+            surrender: SurrenderRule::Late,
+            max_seats: 3,
+        }
+    }
+}
+
+impl BlackjackRules {
+    /// Whether the Bank can deal these rules within its liability limits, with
+    /// a full shoe covering a round at every seat
+    pub fn is_valid(&self) -> bool {
+        (1..=8).contains(&self.decks)
+            && (1..=games::blackjack::MAX_BLACKJACK_HANDS as u8).contains(&self.max_split_hands)
+            && (1..=games::blackjack::MAX_BLACKJACK_SEATS as u8).contains(&self.max_seats)
+            && games::shoe::min_round_cards(self.max_seats as usize) <= self.decks as usize * 52
+    }
+}
+
+/// What a natural pays on top of the returned bet
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, Enum, PartialEq, Eq)]
+pub enum BlackjackPayout {
+    #[default]
+    ThreeToTwo,
+    SixToFive,
+}
+
+/// When the player may give up the initial hand for half the bet
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, Enum, PartialEq, Eq)]
+pub enum SurrenderRule {
+    NotAllowed,
+    /// After the dealer has checked for a natural
+    #[default]
+    Late,
+    /// Before the dealer checks for a natural against an ace or a ten
+    Early,
+}
+
+/// How the Bank's card shoes are built and cut. Blackjack shoes hold
+/// `BlackjackRules::decks` decks.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, SimpleObject)]
+#[serde(default)]
+pub struct ShoeConfig {
+    /// Decks in the baccarat shoe
+    pub baccarat_decks: u8,
+    /// Share of a shoe, in percent, dealt before the cut card comes out
+    pub penetration_percent: u8,
+}
+
+impl Default for ShoeConfig {
+    fn default() -> Self {
+        ShoeConfig {
+            baccarat_decks: 8,
+            penetration_percent: 75,
+        }
+    }
+}
+
+impl ShoeConfig {
+    pub fn is_valid(&self) -> bool {
+        (1..=8).contains(&self.baccarat_decks) && (1..=100).contains(&self.penetration_percent)
+    }
 }
 
 // ============================================================================
@@ -39,8 +224,13 @@ pub struct CasinoParams {
 pub struct CasinoInit {
     /// Starting balance for new players (chips from faucet)
     pub starting_balance: u64,
-    /// Master seed for RNG (only used on Bank chain)
-    pub random_seed: u64,
+    /// Chips set aside for the faucet (only used on Bank chain)
+    #[serde(default = "default_faucet_pool")]
+    pub faucet_pool: u64,
+}
+
+fn default_faucet_pool() -> u64 {
+    100_000
 }
 
 // ============================================================================
@@ -52,8 +242,19 @@ pub enum Operation {
     /// Request chips from the Bank (testnet faucet)
     RequestChips,
     
-    /// Start a Blackjack game with given bet (sends escrow to Bank)
-    PlayBlackjack { bet: u64 },
+    /// Ask the Bank to reserve the seed commitment this chain's next game is
+    /// bound to. Every game needs one, so the client seed is picked once the
+    /// commitment has arrived (`seedCommitment`) and cannot be ground against it.
+    RequestSeedCommitment,
+    
+    /// Start a Blackjack game with given bet (sends escrow to Bank).
+    /// `client_seed` and `nonce` are optional player entropy (see `fairness::game_seed`).
+    /// `side_bets` are escrowed with the bet and settled on the opening cards.
+    PlayBlackjack { bet: u64, client_seed: Option<String>, nonce: Option<u64>, side_bets: Option<SideBets> },
+    
+    /// Play several seats against one dealer hand, one bet per seat (up to
+    /// `BlackjackRules::max_seats`). Seats are dealt from one shuffle and played in order.
+    PlayBlackjackMulti { bets: Vec<u64>, client_seed: Option<String>, nonce: Option<u64> },
     
     /// Hit - draw another card (local computation, then reports to Bank if bust)
     Hit,
@@ -67,14 +268,61 @@ pub enum Operation {
     /// Split - split a pair into two hands (only if first 2 cards match value)
     Split,
 
-    /// Play Roulette with a list of bets
-    PlayRoulette { bets: Vec<RouletteBet> },
+    /// Insurance - stake half the bet against a dealer natural, paid 2:1
+    /// (first decision only, when the dealer shows an ace)
+    Insurance,
 
-    /// Report Roulette result for Bank verification
-    ReportRouletteResult { game_id: u64, claimed_outcome: u8 },
+    /// Even Money - take 1:1 on a natural instead of risking a push
+    /// (first decision only, when the dealer shows an ace)
+    EvenMoney,
+
+    /// Surrender - give up the initial hand and get half the bet back
+    /// (first decision only, see `SurrenderRule`)
+    Surrender,
+
+    /// Play Roulette with a list of bets
+    PlayRoulette { bets: Vec<RouletteBet>, client_seed: Option<String>, nonce: Option<u64> },
 
     /// Start a Baccarat game
-    PlayBaccarat { amount: u64, bet_type: BaccaratBetType },
+    /// `side_bets` are escrowed with the main bet and settled on the same coup.
+    PlayBaccarat {
+        amount: u64,
+        bet_type: BaccaratBetType,
+        client_seed: Option<String>,
+        nonce: Option<u64>,
+        side_bets: Option<Vec<BaccaratSideBet>>,
+    },
+    
+    /// Start a Baccarat game with several main bets (e.g. Player and Tie), all
+    /// settled on one coup
+    PlayBaccaratMulti {
+        wagers: Vec<BaccaratWager>,
+        client_seed: Option<String>,
+        nonce: Option<u64>,
+        side_bets: Option<Vec<BaccaratSideBet>>,
+    },
+
+    /// Clear a stuck blackjack game once its deadline has passed
+    AbandonGame,
+
+    /// Collect every game of this chain the operator has left unrevealed past
+    /// `GameTimeouts::reveal_micros`: each pays back its stakes plus the most
+    /// it could have won
+    ClaimUnrevealed,
+
+    /// Bank only: add commitments to server seeds the operator holds off-chain
+    /// (`fairness::seed_commitment`); each `RequestSeedCommitment` reserves the oldest one
+    CommitSeeds { commitments: Vec<String> },
+
+    /// Bank only: show the cards of a blackjack round. The first reveal is the
+    /// deal, two cards per seat in seat order and then the dealer up-card; later
+    /// ones are the cards drawn for the action the Bank last accepted (see
+    /// `games::blackjack::next_reveal`).
+    RevealCards { game_id: u64, cards: Vec<Card> },
+
+    /// Bank only: reveal the server seed of a finished game, which the Bank
+    /// checks against its commitment and the cards already shown, then settles
+    RevealSeed { game_id: u64, server_seed: String },
 }
 
 // ============================================================================
@@ -93,42 +341,54 @@ pub enum Message {
         player_chain: ChainId,
     },
     
-    /// Start a game with escrowed bet
+    /// Reserve a seed commitment for the chain's next game
+    RequestSeedCommitment {
+        player_chain: ChainId,
+    },
+    
+    /// Start a game with escrowed bets
     RequestGame {
         player: AccountOwner,
         player_chain: ChainId,
         game_type: GameType,
-        bet: u64,
+        bets: Vec<u64>, // One bet per seat
+        commitment: String, // Reserved with `RequestSeedCommitment`
+        client_seed: Option<ClientSeed>,
+        side_bets: SideBets, // Escrowed on top of `bet`
     },
     
-    /// Report game result for verification
-    ReportResult {
+    /// Blackjack decision - the Bank checks it against the cards shown and the
+    /// operator reveals the cards drawn for it
+    PlayerAction {
         game_id: u64,
         player: AccountOwner,
-        actions: Vec<GameAction>,
+        action: GameAction,
+        stake: u64, // Extra chips escrowed for DoubleDown/Split/Insurance (0 otherwise)
     },
 
-    /// Request a Roulette game (player sends bets, bank returns seed)
+    /// Request a Roulette game (the spin comes with the revealed seed)
     RequestRouletteGame {
         player: AccountOwner,
         player_chain: ChainId,
         bets: Vec<RouletteBet>,
-    },
-
-    /// Report roulette result for verification (bank uses stored data for player info)
-    ReportRouletteResult {
-        game_id: u64,
-        claimed_outcome: u8,
+        commitment: String,
+        client_seed: Option<ClientSeed>,
     },
 
     /// Request a Baccarat game
     RequestBaccaratGame {
         player: AccountOwner,
         player_chain: ChainId,
-        amount: u64,
-        bet_type: BaccaratBetType,
+        wagers: Vec<BaccaratWager>,
+        commitment: String,
+        client_seed: Option<ClientSeed>,
+        side_bets: Vec<BaccaratSideBet>, // Escrowed on top of the wagers
     },
 
+    /// Forfeit every game of `player_chain` the operator has not revealed in time
+    ClaimUnrevealed {
+        player_chain: ChainId,
+    },
 
     
     // ─────────────────────────────────────────────────────────────────────────
@@ -141,34 +401,56 @@ pub enum Message {
         amount: u64,
     },
     
-    /// Game ready - here's your seed
+    /// Seed commitment reserved for the chain's next game
+    SeedCommitted {
+        commitment: String,
+    },
+    
+    /// Game ready - commitment to the seed plus the cards the player may see
     GameReady {
         game_id: u64,
-        seed: u64,
-        bet: u64,
+        commitment: String,
+        bets: Vec<u64>, // One bet per seat
+        player_hands: Vec<Vec<Card>>, // Opening hand of each seat
+        dealer_up_card: Card, // The hole card is only revealed at settlement
+        side_bets: Vec<SideBetResult>, // Decided by the deal, paid at settlement
+    },
+
+    /// Cards drawn for a player action (empty for Stand)
+    CardsRevealed {
+        game_id: u64,
+        action: GameAction,
+        cards: Vec<Card>,
     },
     
-    /// Game settled after verification
+    /// Game settled after verification - the seed is revealed for auditing
     GameSettled {
         game_id: u64,
         result: GameResult,
         payout: u64,
+        player_hands: Vec<Vec<Card>>, // Final player hands, including the last drawn card
+        hands: Vec<HandState>, // Stake, flags, result and payout of each player hand
+        seats: Vec<SeatResult>, // Hands of each seat added up
         dealer_hand: Vec<Card>, // Full dealer hand after hitting
+        commitment: String,
+        seed: String, // Server seed; the cards come from `fairness::game_seed(seed, client_seed)`
+        rng_version: rng::RngVersion, // Generator the seed drives
+        client_seed: Option<ClientSeed>,
+        actions: Vec<GameAction>, // Every action the Bank replayed
+        shoe: Shoe, // Shoe the round was dealt from
+        side_bets: Vec<SideBetResult>, // Paid on top of `payout`
     },
 
-    /// Roulette game ready - here's your seed
-    RouletteGameReady {
-        game_id: u64,
-        seed: u64,
-        bets: Vec<RouletteBet>,
-    },
-
-    /// Roulette game settled after verification
+    /// Roulette game settled from the revealed seed
     RouletteSettled {
         game_id: u64,
         outcome: u8,
         payout: u64,
         bets: Vec<RouletteBet>,
+        commitment: String,
+        seed: String,
+        rng_version: rng::RngVersion,
+        client_seed: Option<ClientSeed>,
     },
 
     /// Baccarat game settled
@@ -180,8 +462,74 @@ pub enum Message {
         banker_hand: Vec<Card>,
         player_score: u8,
         banker_score: u8,
-        bet_amount: u64,
-        bet_type: BaccaratBetType,
+        wagers: Vec<BaccaratWagerResult>, // Main bets; `payout` is their total
+        commission_credit: u64, // Hundredths of a chip owed from earlier commissions, added to these payouts
+        commitment: String,
+        seed: String,
+        rng_version: rng::RngVersion,
+        client_seed: Option<ClientSeed>,
+        shoe: Shoe, // Shoe the coup was dealt from
+        side_bets: Vec<BaccaratSideBetResult>, // Paid on top of `payout`
+    },
+
+    /// The operator did not reveal in time: the stakes come back with the most the game could have won
+    GameForfeited {
+        game_id: u64,
+        game_type: GameType,
+        bet: u64, // Everything the player staked on the game
+        payout: u64,
+    },
+
+    /// Escrow or payout returned after one of our messages bounced
+    Refund {
+        game_id: u64,
+        game_type: GameType,
+        amount: u64,
+    },
+}
+
+impl Message {
+    /// Whether this message may only be sent by the Bank chain (Bank → Player)
+    pub fn is_from_bank(&self) -> bool {
+        match self {
+            Message::RequestChips { .. }
+            | Message::RequestSeedCommitment { .. }
+            | Message::RequestGame { .. }
+            | Message::PlayerAction { .. }
+            | Message::RequestRouletteGame { .. }
+            | Message::RequestBaccaratGame { .. }
+            | Message::ClaimUnrevealed { .. } => false,
+            Message::ChipsGranted { .. }
+            | Message::SeedCommitted { .. }
+            | Message::GameReady { .. }
+            | Message::CardsRevealed { .. }
+            | Message::GameSettled { .. }
+            | Message::RouletteSettled { .. }
+            | Message::BaccaratSettled { .. }
+            | Message::GameForfeited { .. }
+            | Message::Refund { .. } => true,
+        }
+    }
+}
+
+// ============================================================================
+// EVENTS (emitted on the Bank chain)
+// ============================================================================
+
+/// Stream the Bank announces shoe changes on
+pub const SHOE_STREAM: &[u8] = b"shoe";
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum CasinoEvent {
+    /// The cut card came out and the shoe was rebuilt from full decks
+    ShoeReshuffled {
+        game_type: GameType,
+        /// Table of the shoe among the game's shoes
+        table: u32,
+        /// Reshuffles of this shoe so far, this one included
+        shuffles: u64,
+        /// Cards dealt from the shoe before the reshuffle
+        cards_dealt: u32,
     },
 }
 
@@ -202,6 +550,9 @@ pub enum GameAction {
     Stand,
     DoubleDown,
     Split,
+    Insurance,
+    EvenMoney,
+    Surrender,
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize, Enum, PartialEq, Eq)]
@@ -212,13 +563,22 @@ pub enum GameResult {
     PlayerBust,
     DealerBust,
     Push,
+    /// Player gave up the hand; half the bet was returned
+    Surrender,
+    /// Game voided after a bounced message; the stake (or payout) was returned
+    Refunded,
+    /// Player abandoned a stuck game; a later settlement from the Bank is still credited
+    Abandoned,
+    /// The operator did not reveal in time; the player was paid the most the game could win
+    Forfeited,
 }
 
 // ============================================================================
 // CARD TYPES (shared between chains)
 // ============================================================================
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, SimpleObject)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, SimpleObject, InputObject)]
+#[graphql(input_name = "CardInput")]
 pub struct Card {
     pub suit: String,
     pub value: String,
@@ -236,6 +596,98 @@ impl Card {
     }
 }
 
+/// Stake and progress of one blackjack hand (a round has several after splits)
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, SimpleObject)]
+pub struct HandState {
+    pub bet: u64,
+    /// Bet doubled and one card taken
+    pub doubled: bool,
+    /// Hand came out of a split, so 21 on two cards is not a natural
+    pub from_split: bool,
+    /// No more actions are taken on this hand
+    pub finished: bool,
+    /// Outcome of this hand, set at settlement
+    pub result: Option<GameResult>,
+    /// Chips returned for this hand, set at settlement
+    pub payout: u64,
+    /// Seat the hand is played on (split hands stay on their seat)
+    pub seat: u32,
+}
+
+impl HandState {
+    pub fn new(bet: u64) -> Self {
+        HandState { bet, ..Default::default() }
+    }
+}
+
+/// Settlement of one blackjack seat, all its split hands together
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, SimpleObject)]
+pub struct SeatResult {
+    pub seat: u32,
+    /// Bet the seat was dealt with
+    pub bet: u64,
+    /// Everything staked on the seat, doubles and splits included
+    pub staked: u64,
+    pub result: GameResult,
+    pub payout: u64,
+}
+
+/// Optional blackjack side-bet stakes, each at most the main bet
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq, InputObject)]
+pub struct SideBets {
+    /// Perfect Pairs: the first two player cards form a pair
+    pub perfect_pairs: u64,
+    /// 21+3: poker hand of the first two player cards and the dealer up-card
+    pub twenty_one_plus_three: u64,
+}
+
+impl SideBets {
+    /// Both stakes together; panics rather than wrap if they overflow a u64
+    pub fn total(&self) -> u64 {
+        self.perfect_pairs
+            .checked_add(self.twenty_one_plus_three)
+            .expect("Side bet stakes overflow")
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Enum, PartialEq, Eq)]
+pub enum SideBetKind {
+    PerfectPairs,
+    TwentyOnePlusThree,
+}
+
+/// Winning side-bet hands
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Enum, PartialEq, Eq)]
+pub enum SideBetOutcome {
+    /// Perfect Pairs: same rank, red and black (6:1)
+    MixedPair,
+    /// Perfect Pairs: same rank and colour, different suits (12:1)
+    ColouredPair,
+    /// Perfect Pairs: the same card twice (25:1)
+    PerfectPair,
+    /// 21+3: one suit (5:1)
+    Flush,
+    /// 21+3: consecutive ranks, ace high or low (10:1)
+    Straight,
+    /// 21+3: one rank (30:1)
+    ThreeOfAKind,
+    /// 21+3: consecutive ranks in one suit (40:1)
+    StraightFlush,
+    /// 21+3: the same card three times (100:1)
+    SuitedTrips,
+}
+
+/// A side bet settled on the opening cards
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, SimpleObject)]
+pub struct SideBetResult {
+    pub kind: SideBetKind,
+    pub stake: u64,
+    /// Winning hand, if any
+    pub outcome: Option<SideBetOutcome>,
+    /// Chips returned, stake included (0 if lost)
+    pub payout: u64,
+}
+
 // ============================================================================
 // LEGACY TYPES (for roulette/baccarat - to be migrated later)
 // ============================================================================
@@ -247,6 +699,66 @@ pub enum BaccaratBetType {
     Tie,
 }
 
+/// Baccarat rule variant, which only changes what a winning Banker bet pays
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, Enum, PartialEq, Eq)]
+pub enum BaccaratVariant {
+    /// Banker pays 0.95:1 (5% commission), settled to the hundredth of a chip
+    #[default]
+    Classic,
+    /// No commission, but a Banker win with 6 pays 1:2
+    SuperSix,
+    /// No commission, but a Banker win with a three-card 7 (Dragon 7) pushes
+    Ez,
+}
+
+/// A main baccarat bet
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, InputObject)]
+pub struct BaccaratWager {
+    pub bet_type: BaccaratBetType,
+    pub amount: u64,
+}
+
+/// A main baccarat bet settled on the coup
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, SimpleObject)]
+pub struct BaccaratWagerResult {
+    pub bet_type: BaccaratBetType,
+    pub amount: u64,
+    /// Chips returned, stake included (0 if lost)
+    pub payout: u64,
+}
+
+/// Baccarat side bets, settled on the same coup as the main bet
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Enum, PartialEq, Eq)]
+pub enum BaccaratSideBetType {
+    /// The Player's first two cards are a pair (11:1)
+    PlayerPair,
+    /// The Banker's first two cards are a pair (11:1)
+    BankerPair,
+    /// Either side's first two cards are a pair (5:1)
+    EitherPair,
+    /// Either side's first two cards are the same card twice (25:1)
+    PerfectPair,
+    /// Dragon Bonus on the Player: paid by natural or winning margin
+    DragonPlayer,
+    /// Dragon Bonus on the Banker: paid by natural or winning margin
+    DragonBanker,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, InputObject)]
+pub struct BaccaratSideBet {
+    pub bet_type: BaccaratSideBetType,
+    pub amount: u64,
+}
+
+/// A baccarat side bet settled on the coup
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, SimpleObject)]
+pub struct BaccaratSideBetResult {
+    pub bet_type: BaccaratSideBetType,
+    pub amount: u64,
+    /// Chips returned, stake included (0 if lost)
+    pub payout: u64,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, InputObject)]
 pub struct RouletteBet {
     pub bet_type: RouletteBetType,
@@ -0,0 +1,123 @@
+//! Random number generators used to shuffle decks and spin the wheel.
+//!
+//! Every game records the `RngVersion` it was played with, so games dealt by an
+//! older generator still replay to the same cards after an upgrade.
+
+use async_graphql::Enum;
+use rand_chacha::rand_core::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Domain separator for expanding a game seed into a ChaCha20 key
+pub const CHACHA_KEY_DOMAIN_V1: &[u8] = b"linerabet/rng/chacha20/v1";
+
+/// Source of randomness for one game
+pub trait GameRng {
+    fn next_u64(&mut self) -> u64;
+
+    /// Uniform value in `0..bound`, using rejection sampling to avoid modulo bias
+    fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "bound must be positive");
+        // Largest multiple of `bound` that fits in a u64; values past it are redrawn
+        let zone = u64::MAX - (u64::MAX % bound);
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return value % bound;
+            }
+        }
+    }
+
+    /// Index drawn for the Fisher-Yates swap at a position with `bound` choices
+    fn shuffle_index(&mut self, bound: u64) -> u64 {
+        self.below(bound)
+    }
+}
+
+/// Which generator a game was played with
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Enum)]
+pub enum RngVersion {
+    /// 64-bit xorshift with `next() % n` sampling (32-bit draws when shuffling).
+    /// Kept only to replay old games.
+    XorShift,
+    /// ChaCha20 keyed by SHA-256 of `CHACHA_KEY_DOMAIN_V1` followed by the
+    /// big-endian seed, with rejection sampling
+    #[default]
+    ChaCha20V1,
+}
+
+impl RngVersion {
+    /// Version used for new games
+    pub const CURRENT: RngVersion = RngVersion::ChaCha20V1;
+
+    /// Generator for `seed` under this version
+    pub fn rng(self, seed: u64) -> Box<dyn GameRng> {
+        match self {
+            RngVersion::XorShift => Box::new(XorShiftRng::new(seed)),
+            RngVersion::ChaCha20V1 => Box::new(ChaChaGameRng::new(seed)),
+        }
+    }
+}
+
+/// Fisher-Yates shuffle driven by `rng`
+pub fn shuffle<T>(items: &mut [T], rng: &mut dyn GameRng) {
+    for i in (1..items.len()).rev() {
+        let j = rng.shuffle_index(i as u64 + 1) as usize;
+        items.swap(i, j);
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Generators
+// ────────────────────────────────────────────────────────────────────────────
+
+/// The original xorshift generator (formerly `SimpleRng`)
+pub struct XorShiftRng(u64);
+
+impl XorShiftRng {
+    pub fn new(seed: u64) -> Self {
+        let seed = if seed == 0 { 0x9e3779b185ebca87 } else { seed };
+        XorShiftRng(seed)
+    }
+}
+
+impl GameRng for XorShiftRng {
+    fn next_u64(&mut self) -> u64 {
+        let mut value = self.0;
+        value ^= value << 7;
+        value ^= value >> 9;
+        value ^= value << 8;
+        self.0 = value;
+        self.0
+    }
+
+    /// Plain modulo, exactly as the original games were dealt
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    /// The original shuffle ran on wasm32 and took `next() as usize % n`,
+    /// so only the low 32 bits of each draw picked the card
+    fn shuffle_index(&mut self, bound: u64) -> u64 {
+        (self.next_u64() as u32 as u64) % bound
+    }
+}
+
+/// ChaCha20 stream keyed from the game seed
+pub struct ChaChaGameRng(ChaCha20Rng);
+
+impl ChaChaGameRng {
+    pub fn new(seed: u64) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(CHACHA_KEY_DOMAIN_V1);
+        hasher.update(seed.to_be_bytes());
+        ChaChaGameRng(ChaCha20Rng::from_seed(hasher.finalize().into()))
+    }
+}
+
+impl GameRng for ChaChaGameRng {
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+}
@@ -6,17 +6,24 @@ use std::sync::Arc;
 
 use async_graphql::{EmptySubscription, Object, Schema, SimpleObject};
 use futures::lock::Mutex;
+use serde::Serialize;
 
 use linera_sdk::{
     graphql::GraphQLMutationRoot,
-    linera_base_types::WithServiceAbi,
+    linera_base_types::{AccountOwner, WithServiceAbi},
     views::View,
     Service, ServiceRuntime,
 };
 
-use contracts::{Operation, Card, GameResult, GameType, CasinoParams, RouletteBet, RouletteBetType, BaccaratBetType};
+use contracts::fairness::{game_seed, seed_commitment, verify_seed_commitment, ClientSeed};
+use contracts::games::baccarat::{calculate_baccarat_payout, run_baccarat_game, settle_baccarat_side_bets, settle_baccarat_wagers};
+use contracts::games::blackjack::{self, available_actions, calculate_hand_value, ALLOWED_BETS};
+use contracts::games::roulette::{calculate_roulette_payout, spin_roulette};
+use contracts::games::shoe::{Shoe, ShoeTable};
+use contracts::rng::RngVersion;
+use contracts::{BlackjackRules, Operation, Card, FaucetConfig, GameAction, GameResult, GameType, GameTimeouts, CasinoParams, HandState, ShoeConfig, RouletteBet, RouletteBetType, BaccaratBetType, BaccaratSideBet, BaccaratSideBetResult, BaccaratVariant, BaccaratWager, BaccaratWagerResult, SeatResult, SideBetResult};
 
-use self::state::{ContractsState, ActiveGame, GamePhase, GameRecord, PendingRouletteGame, ALLOWED_BETS};
+use self::state::{ContractsState, ActiveGame, GamePhase, GameRecord, PendingGame};
 
 pub struct ContractsService {
     state: Arc<Mutex<ContractsState>>,
@@ -77,18 +84,110 @@ impl QueryRoot {
         current_chain == params.bank_chain_id
     }
     
+    /// Deadlines for player decisions and the operator's reveals
+    async fn game_timeouts(&self) -> GameTimeouts {
+        self.runtime.application_parameters().timeouts
+    }
+    
     /// Default chip amount for faucet
     async fn default_buy_in(&self) -> u64 {
         let state = self.state.lock().await;
         *state.default_buy_in.get()
     }
     
+    /// Faucet cooldown and lifetime cap
+    async fn faucet_config(&self) -> FaucetConfig {
+        self.runtime.application_parameters().faucet
+    }
+
+    /// House rules blackjack is dealt and replayed with
+    async fn blackjack_rules(&self) -> BlackjackRules {
+        self.runtime.application_parameters().blackjack_rules
+    }
+    
+    /// Shoe sizes and cut-card penetration
+    async fn shoe_config(&self) -> ShoeConfig {
+        self.runtime.application_parameters().shoe
+    }
+    
+    /// How winning Banker bets are paid in baccarat
+    async fn baccarat_variant(&self) -> BaccaratVariant {
+        self.runtime.application_parameters().baccarat_variant
+    }
+    
+    /// Hundredths of a chip the player is owed from Classic baccarat
+    /// commissions, paid with a later Banker win (only relevant on Bank chain)
+    async fn baccarat_commission_credit(&self, owner: AccountOwner) -> u64 {
+        let state = self.state.lock().await;
+        state.baccarat_commission_credit.get(&owner).await
+            .ok()
+            .flatten()
+            .unwrap_or(0)
+    }
+    
+    /// A card game's shoes, one per table, with the round each is dealing (only
+    /// relevant on Bank chain; empty before the game's first round)
+    async fn shoes(&self, game_type: GameType) -> Vec<ShoeTable> {
+        let state = self.state.lock().await;
+        match game_type {
+            GameType::Blackjack => state.blackjack_shoes.get().clone(),
+            GameType::Baccarat => state.baccarat_shoes.get().clone(),
+            GameType::Roulette => Vec::new(),
+        }
+    }
+    
+    /// Chips left in the faucet pool (only relevant on Bank chain)
+    async fn faucet_pool(&self) -> u64 {
+        let state = self.state.lock().await;
+        *state.faucet_pool.get()
+    }
+    
+    /// Earliest time (micros) the player may claim from the faucet again,
+    /// or null once their lifetime cap is reached (only relevant on Bank chain)
+    async fn faucet_next_claim_at(&self, owner: AccountOwner) -> Option<u64> {
+        let state = self.state.lock().await;
+        let config = self.runtime.application_parameters().faucet;
+        let claim = state.faucet_claims.get(&owner).await
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        claim.next_claim_at(&config, *state.default_buy_in.get())
+    }
+    
     /// House balance (only relevant on Bank chain)
     async fn house_balance(&self) -> u64 {
         let state = self.state.lock().await;
         *state.house_balance.get()
     }
     
+    /// Seed commitments the operator has published that no game has taken yet
+    /// (only relevant on Bank chain)
+    async fn seed_commitments_left(&self) -> u64 {
+        let state = self.state.lock().await;
+        state.seed_commitments.count() as u64
+    }
+    
+    /// Games waiting on a player decision or on the operator, with everything
+    /// the operator needs to work out its next reveal (only relevant on Bank chain)
+    async fn pending_games(&self) -> Vec<PendingGameObject> {
+        let state = self.state.lock().await;
+        let rules = self.runtime.application_parameters().blackjack_rules;
+        let game_ids = state.pending_games.indices().await.unwrap_or_default();
+        let mut games = Vec::new();
+        for game_id in game_ids {
+            if let Ok(Some(pending)) = state.pending_games.get(&game_id).await {
+                games.push(PendingGameObject::new(game_id, pending, &rules));
+            }
+        }
+        games
+    }
+    
+    /// Worst-case payouts reserved for open games (only relevant on Bank chain)
+    async fn outstanding_liability(&self) -> u64 {
+        let state = self.state.lock().await;
+        *state.outstanding_liability.get()
+    }
+    
     /// Player's chip balance
     async fn player_balance(&self) -> u64 {
         let state = self.state.lock().await;
@@ -98,7 +197,15 @@ impl QueryRoot {
     /// Current active game (if any)
     async fn current_game(&self) -> Option<CurrentGameObject> {
         let state = self.state.lock().await;
-        state.current_game.get().as_ref().map(|g| CurrentGameObject::from(g.clone()))
+        let rules = self.runtime.application_parameters().blackjack_rules;
+        state.current_game.get().as_ref().map(|g| CurrentGameObject::new(g.clone(), &rules))
+    }
+    
+    /// Seed commitment the Bank reserved for this chain's next game; null until
+    /// `requestSeedCommitment` is answered and again once a game uses it
+    async fn seed_commitment(&self) -> Option<String> {
+        let state = self.state.lock().await;
+        state.seed_commitment.get().clone()
     }
     
     /// Game history
@@ -119,10 +226,28 @@ impl QueryRoot {
         ALLOWED_BETS.to_vec()
     }
 
-    /// Pending roulette game (if any) - for immediate result display
-    async fn pending_roulette(&self) -> Option<PendingRouletteObject> {
+    /// Recompute the seed a settled game was played with from its revealed
+    /// server seed and the player's client seed (null if the game is not in history)
+    async fn client_seed_check(&self, game_id: u64) -> Option<ClientSeedCheckObject> {
         let state = self.state.lock().await;
-        state.pending_roulette.get().as_ref().map(|g| PendingRouletteObject::from(g.clone()))
+        let record = find_record(&state, game_id).await?;
+        let server_seed = record.seed?;
+        Some(ClientSeedCheckObject {
+            game_id,
+            game_seed: game_seed(&server_seed, record.client_seed.as_ref()),
+            commitment_valid: record.commitment.as_ref()
+                .map(|commitment| verify_seed_commitment(commitment, &server_seed)),
+            server_seed,
+            client_seed: record.client_seed,
+        })
+    }
+
+    /// Replay a settled game from its revealed seed and compare every recorded
+    /// card, outcome and payout (null if the game is not in history)
+    async fn verify_game(&self, game_id: u64) -> Option<VerificationReport> {
+        let state = self.state.lock().await;
+        let record = find_record(&state, game_id).await?;
+        Some(verify_record(record))
     }
 
 
@@ -135,34 +260,60 @@ impl QueryRoot {
 #[derive(SimpleObject)]
 struct CurrentGameObject {
     game_id: u64,
-    seed: u64,
+    commitment: String,
     bet: u64,
     game_type: GameType,
+    started_at: u64,
     phase: GamePhase,
     player_hands: Vec<Vec<CardObject>>,
+    /// Stake and progress of each player hand
+    hands: Vec<HandState>,
     active_hand_index: u32,
     dealer_hand: Vec<CardObject>,
     player_values: Vec<u8>,
     dealer_value: u8,
+    /// Insurance stake escrowed against a dealer natural (0 if none)
+    insurance: u64,
+    /// Side bets settled at the deal (paid when the round settles)
+    side_bets: Vec<SideBetResult>,
+    /// Actions the player may send now (empty outside `PLAYER_TURN`)
+    available_actions: Vec<GameAction>,
 }
 
-impl From<ActiveGame> for CurrentGameObject {
-    fn from(g: ActiveGame) -> Self {
+impl CurrentGameObject {
+    fn new(g: ActiveGame, rules: &BlackjackRules) -> Self {
         let player_values = g.player_hands.iter().map(|h| calculate_hand_value(h)).collect();
         let dealer_value = calculate_hand_value(&g.dealer_hand);
+        let available_actions = if g.phase == GamePhase::PlayerTurn {
+            available_actions(
+                &g.player_hands,
+                &g.hands,
+                g.active_hand_index as usize,
+                &g.dealer_hand[0],
+                &g.actions,
+                rules,
+            )
+        } else {
+            Vec::new()
+        };
         CurrentGameObject {
             game_id: g.game_id,
-            seed: g.seed,
+            commitment: g.commitment,
             bet: g.bet,
             game_type: g.game_type,
+            started_at: g.started_at,
             phase: g.phase,
             player_hands: g.player_hands.into_iter()
                 .map(|hand| hand.into_iter().map(CardObject::from).collect())
                 .collect(),
+            hands: g.hands,
             active_hand_index: g.active_hand_index,
             dealer_hand: g.dealer_hand.into_iter().map(CardObject::from).collect(),
             player_values,
             dealer_value,
+            insurance: g.insurance,
+            side_bets: g.side_bets,
+            available_actions,
         }
     }
 }
@@ -189,15 +340,75 @@ struct GameRecordObject {
     game_id: u64,
     game_type: GameType,
     player_hands: Vec<Vec<CardObject>>,
+    /// Per-hand stake, flags and result (blackjack only)
+    hands: Vec<HandState>,
+    /// Per-seat bet and result (blackjack only)
+    seats: Vec<SeatResult>,
     dealer_hand: Vec<CardObject>,
     bet: u64,
     result: GameResult,
     payout: u64,
     timestamp: u64,
+    seed: Option<String>,
+    rng_version: Option<RngVersion>,
+    client_seed: Option<ClientSeed>,
+    commitment: Option<String>,
+    actions: Vec<GameAction>,
     roulette_bets: Option<Vec<RouletteBetObject>>,
     roulette_outcome: Option<u8>,
     baccarat_winner: Option<BaccaratBetType>,
     baccarat_bet: Option<BaccaratBetType>,
+    /// Baccarat main bets; `bet` and `payout` are their totals
+    baccarat_wagers: Vec<BaccaratWagerResult>,
+    /// Hundredths of a chip from earlier commissions added to the baccarat payouts
+    baccarat_commission_credit: u64,
+    /// Baccarat side bets, paid on top of `payout`
+    baccarat_side_bets: Vec<BaccaratSideBetResult>,
+    shoe: Option<Shoe>,
+    /// Blackjack side bets, paid on top of `payout`
+    side_bets: Vec<SideBetResult>,
+    /// Blackjack rules the game was dealt under
+    blackjack_rules: Option<BlackjackRules>,
+    /// Baccarat variant the game was paid under
+    baccarat_variant: Option<BaccaratVariant>,
+}
+
+#[derive(SimpleObject)]
+struct ClientSeedCheckObject {
+    game_id: u64,
+    server_seed: String,
+    client_seed: Option<ClientSeed>,
+    /// Seed the cards or spin were drawn from
+    game_seed: u64,
+    /// Whether the server seed matches the commitment the game was bound to
+    commitment_valid: Option<bool>,
+}
+
+/// Outcome of `verifyGame`
+#[derive(SimpleObject)]
+struct VerificationReport {
+    game_id: u64,
+    game_type: GameType,
+    /// False when no seed was revealed (refunded or abandoned games) or the
+    /// record lacks the generator, shoe or rules needed to replay it
+    verifiable: bool,
+    /// Verifiable and every check passed
+    passed: bool,
+    /// Seed the game was replayed from (server seed mixed with the client seed)
+    game_seed: Option<u64>,
+    rng_version: Option<RngVersion>,
+    checks: Vec<VerificationCheck>,
+    /// Names of the failed checks
+    mismatches: Vec<String>,
+}
+
+/// One recomputed value compared against history (values are JSON-encoded)
+#[derive(SimpleObject)]
+struct VerificationCheck {
+    name: String,
+    passed: bool,
+    expected: String,
+    recorded: String,
 }
 
 #[derive(SimpleObject)]
@@ -219,27 +430,60 @@ impl From<RouletteBet> for RouletteBetObject {
     }
 }
 
+/// A game pending on the Bank chain
 #[derive(SimpleObject)]
-struct PendingRouletteObject {
+struct PendingGameObject {
     game_id: u64,
-    seed: u64,
-    outcome: u8,
-    bets: Vec<RouletteBetObject>,
+    game_type: GameType,
+    player_chain: String,
+    bet: u64,
+    /// Commitment to the server seed the game is dealt from
+    commitment: String,
+    client_seed: Option<ClientSeed>,
+    rng_version: RngVersion,
+    created_at: u64,
+    /// When the Bank started waiting on the player or the operator
+    waiting_since: u64,
+    /// Whether the operator owes the next step (a deal, cards or the seed)
+    awaits_operator: bool,
+    /// Every chip escrowed for the game
+    staked: u64,
+    max_liability: u64,
+    /// Blackjack: bet of each seat, actions accepted so far and the cards shown
+    /// (null until dealt)
+    seat_bets: Vec<u64>,
+    actions: Vec<GameAction>,
+    round: Option<CurrentGameObject>,
+    /// Blackjack: how many of `actions` have had their cards revealed (null until dealt)
+    revealed: Option<u32>,
+    /// Shoe a card game is dealt from
+    shoe: Option<Shoe>,
 }
 
-impl From<PendingRouletteGame> for PendingRouletteObject {
-    fn from(g: PendingRouletteGame) -> Self {
-        PendingRouletteObject {
-            game_id: g.game_id,
-            seed: g.seed,
-            outcome: g.outcome,
-            bets: g.bets.into_iter().map(RouletteBetObject::from).collect(),
+impl PendingGameObject {
+    fn new(game_id: u64, pending: PendingGame, rules: &BlackjackRules) -> Self {
+        PendingGameObject {
+            game_id,
+            game_type: pending.game_type,
+            player_chain: pending.player_chain.to_string(),
+            bet: pending.bet,
+            awaits_operator: pending.awaits_operator(),
+            commitment: pending.commitment,
+            client_seed: pending.client_seed,
+            rng_version: pending.rng_version,
+            created_at: pending.created_at,
+            waiting_since: pending.waiting_since,
+            staked: pending.staked,
+            max_liability: pending.max_liability,
+            seat_bets: pending.seat_bets,
+            actions: pending.actions,
+            revealed: pending.round.as_ref().map(|round| round.actions.len() as u32),
+            round: pending.round.map(|round| CurrentGameObject::new(round, rules)),
+            shoe: pending.shoe,
         }
     }
 }
 
-
-
 impl From<GameRecord> for GameRecordObject {
     fn from(r: GameRecord) -> Self {
         GameRecordObject {
@@ -248,15 +492,29 @@ impl From<GameRecord> for GameRecordObject {
             player_hands: r.player_hands.into_iter()
                 .map(|hand| hand.into_iter().map(CardObject::from).collect())
                 .collect(),
+            hands: r.hands,
+            seats: r.seats,
             dealer_hand: r.dealer_hand.into_iter().map(CardObject::from).collect(),
             bet: r.bet,
             result: r.result,
             payout: r.payout,
             timestamp: r.timestamp,
+            seed: r.seed,
+            rng_version: r.rng_version,
+            client_seed: r.client_seed,
+            commitment: r.commitment,
+            actions: r.actions,
             roulette_bets: r.roulette_bets.map(|bets| bets.into_iter().map(RouletteBetObject::from).collect()),
             roulette_outcome: r.roulette_outcome,
             baccarat_winner: r.baccarat_winner,
             baccarat_bet: r.baccarat_bet,
+            baccarat_wagers: r.baccarat_wagers,
+            baccarat_commission_credit: r.baccarat_commission_credit,
+            baccarat_side_bets: r.baccarat_side_bets,
+            shoe: r.shoe,
+            side_bets: r.side_bets,
+            blackjack_rules: r.blackjack_rules,
+            baccarat_variant: r.baccarat_variant,
         }
     }
 }
@@ -265,28 +523,131 @@ impl From<GameRecord> for GameRecordObject {
 // Helper Functions
 // ============================================================================
 
-fn calculate_hand_value(cards: &[Card]) -> u8 {
-    let mut total = 0u8;
-    let mut aces = 0u8;
-
-    for card in cards {
-        match card.value.as_str() {
-            "ace" => {
-                aces += 1;
-                total = total.saturating_add(11);
-            }
-            "king" | "queen" | "jack" => total = total.saturating_add(10),
-            value => {
-                let parsed = value.parse::<u8>().unwrap_or(0);
-                total = total.saturating_add(parsed);
+/// Most recent history entry for `game_id`
+async fn find_record(state: &ContractsState, game_id: u64) -> Option<GameRecord> {
+    let count = state.game_history.count();
+    for i in (0..count).rev() {
+        if let Ok(Some(record)) = state.game_history.get(i).await {
+            if record.game_id == game_id {
+                return Some(record);
             }
         }
     }
+    None
+}
 
-    while total > 21 && aces > 0 {
-        total -= 10;
-        aces -= 1;
+/// Replay a history entry with the shared game engines
+fn verify_record(record: GameRecord) -> VerificationReport {
+    let mut report = VerificationReport {
+        game_id: record.game_id,
+        game_type: record.game_type,
+        verifiable: false,
+        passed: false,
+        game_seed: None,
+        rng_version: None,
+        checks: Vec::new(),
+        mismatches: Vec::new(),
+    };
+    // Only what the record holds is replayed, so a game missing its generator,
+    // shoe or table rules is reported as unverifiable rather than guessed at
+    let (Some(server_seed), Some(rng_version)) = (record.seed, record.rng_version) else {
+        return report;
+    };
+    let seed = game_seed(&server_seed, record.client_seed.as_ref());
+    
+    let checks = &mut report.checks;
+    if let Some(commitment) = &record.commitment {
+        push_check(checks, "commitment", &seed_commitment(&server_seed), commitment);
     }
+    match record.game_type {
+        GameType::Blackjack => {
+            let (Some(rules), Some(shoe)) = (record.blackjack_rules, record.shoe.clone()) else {
+                return report;
+            };
+            // Records without seats were dealt to a single seat
+            let seat_bets: Vec<u64> = if record.seats.is_empty() {
+                vec![record.bet]
+            } else {
+                record.seats.iter().map(|seat| seat.bet).collect()
+            };
+            let table = blackjack::replay_seats(&shoe, seed, rng_version, &seat_bets, rules, &record.actions);
+            let (result, payout, player_hands, hands, dealer_hand) = table.settle();
+            push_check(checks, "playerHands", &player_hands, &record.player_hands);
+            push_check(checks, "hands", &hands, &record.hands);
+            if !record.seats.is_empty() {
+                push_check(checks, "seats", &blackjack::seat_results(&hands, &seat_bets), &record.seats);
+            }
+            push_check(checks, "dealerHand", &dealer_hand, &record.dealer_hand);
+            push_check(checks, "result", &result, &record.result);
+            push_check(checks, "payout", &payout, &record.payout);
+            if !record.side_bets.is_empty() {
+                // Side bets only see the opening cards
+                let dealt = blackjack::BlackjackTable::deal(&shoe, seed, rng_version, record.bet, rules);
+                let side_bets: Vec<SideBetResult> = record.side_bets.iter()
+                    .map(|side_bet| blackjack::settle_side_bet(side_bet.kind, side_bet.stake, &dealt.player_hands[0], &dealt.dealer_hand[0]))
+                    .collect();
+                push_check(checks, "sideBets", &side_bets, &record.side_bets);
+            }
+        }
+        GameType::Roulette => {
+            let outcome = spin_roulette(seed, rng_version);
+            let bets = record.roulette_bets.unwrap_or_default();
+            push_check(checks, "outcome", &Some(outcome), &record.roulette_outcome);
+            push_check(checks, "payout", &calculate_roulette_payout(&bets, outcome), &record.payout);
+        }
+        GameType::Baccarat => {
+            let (Some(variant), Some(shoe)) = (record.baccarat_variant, record.shoe.clone()) else {
+                return report;
+            };
+            let (winner, player_hand, banker_hand, _, _) = run_baccarat_game(&shoe, seed, rng_version);
+            push_check(checks, "playerHand", &player_hand, record.player_hands.first().unwrap_or(&Vec::new()));
+            push_check(checks, "bankerHand", &banker_hand, &record.dealer_hand);
+            push_check(checks, "winner", &Some(winner), &record.baccarat_winner);
+            let payout = if record.baccarat_wagers.is_empty() {
+                record.baccarat_bet
+                    .map(|bet_type| calculate_baccarat_payout(variant, bet_type, winner, &banker_hand, record.bet))
+            } else {
+                let staked: Vec<BaccaratWager> = record.baccarat_wagers.iter()
+                    .map(|wager| BaccaratWager { bet_type: wager.bet_type, amount: wager.amount })
+                    .collect();
+                let (wagers, _) = settle_baccarat_wagers(
+                    variant,
+                    &staked,
+                    winner,
+                    &banker_hand,
+                    record.baccarat_commission_credit,
+                );
+                push_check(checks, "baccaratWagers", &wagers, &record.baccarat_wagers);
+                Some(wagers.iter().map(|wager| wager.payout).sum())
+            };
+            push_check(checks, "payout", &payout, &Some(record.payout));
+            if !record.baccarat_side_bets.is_empty() {
+                let staked: Vec<BaccaratSideBet> = record.baccarat_side_bets.iter()
+                    .map(|side_bet| BaccaratSideBet { bet_type: side_bet.bet_type, amount: side_bet.amount })
+                    .collect();
+                let side_bets = settle_baccarat_side_bets(&staked, &player_hand, &banker_hand);
+                push_check(checks, "baccaratSideBets", &side_bets, &record.baccarat_side_bets);
+            }
+        }
+    }
+    
+    report.verifiable = true;
+    report.game_seed = Some(seed);
+    report.rng_version = Some(rng_version);
+    report.mismatches = report.checks.iter()
+        .filter(|check| !check.passed)
+        .map(|check| check.name.clone())
+        .collect();
+    report.passed = report.mismatches.is_empty();
+    report
+}
 
-    total
+fn push_check<T: PartialEq + Serialize>(checks: &mut Vec<VerificationCheck>, name: &str, expected: &T, recorded: &T) {
+    checks.push(VerificationCheck {
+        name: name.to_string(),
+        passed: expected == recorded,
+        expected: serde_json::to_string(expected).unwrap_or_default(),
+        recorded: serde_json::to_string(recorded).unwrap_or_default(),
+    });
 }
+
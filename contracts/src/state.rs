@@ -1,11 +1,12 @@
 use async_graphql::Enum;
-use linera_sdk::views::{linera_views, MapView, RegisterView, RootView, ViewStorageContext, LogView};
+use linera_sdk::views::{linera_views, MapView, QueueView, RegisterView, RootView, ViewStorageContext, LogView};
 use serde::{Deserialize, Serialize};
 use linera_base::identifiers::{AccountOwner as Owner, ChainId};
 
-use contracts::{Card, GameAction, GameResult, GameType, RouletteBet};
-
-pub const ALLOWED_BETS: [u64; 5] = [1, 2, 3, 4, 5];
+use contracts::fairness::ClientSeed;
+use contracts::games::shoe::{Shoe, ShoeTable};
+use contracts::rng::RngVersion;
+use contracts::{BaccaratSideBet, BaccaratVariant, BaccaratWager, BlackjackRules, Card, FaucetConfig, GameAction, GameResult, GameType, HandState, RouletteBet, SeatResult, SideBetResult, SideBets};
 
 // ============================================================================
 // MAIN CONTRACT STATE
@@ -17,9 +18,6 @@ pub struct ContractsState {
     // Note: bank_chain_id is now in Application Parameters, not state!
     // Access via runtime.application_parameters().bank_chain_id
     
-    /// Master seed for RNG (Bank uses this)
-    pub master_seed: RegisterView<u64>,
-    
     /// Default chip amount for faucet
     pub default_buy_in: RegisterView<u64>,
     
@@ -30,14 +28,38 @@ pub struct ContractsState {
     /// House balance (chips available for payouts)
     pub house_balance: RegisterView<u64>,
     
-    /// Pending games awaiting player actions or verification
+    /// Commitments to server seeds the operator holds off-chain, oldest first
+    pub seed_commitments: QueueView<String>,
+    
+    /// Commitment reserved for each player chain's next game
+    pub seed_reservations: MapView<ChainId, String>,
+    
+    /// Pending games awaiting player actions or the operator's reveals
     pub pending_games: MapView<u64, PendingGame>,
 
+    /// Sum of `max_liability` over all pending games
+    pub outstanding_liability: RegisterView<u64>,
+
     /// Pending roulette bets (bank chain - for proper payout calculation)
     pub pending_roulette_bets: MapView<u64, Vec<RouletteBet>>,
     
+    /// Chips left for the faucet to hand out
+    pub faucet_pool: RegisterView<u64>,
+
+    /// Faucet claims per player
+    pub faucet_claims: MapView<Owner, FaucetClaim>,
+
     /// Counter for generating unique game IDs
     pub game_counter: RegisterView<u64>,
+
+    /// Blackjack shoes, one per table (the first built on the first round)
+    pub blackjack_shoes: RegisterView<Vec<ShoeTable>>,
+
+    /// Baccarat shoes, one per table (the first built on the first coup)
+    pub baccarat_shoes: RegisterView<Vec<ShoeTable>>,
+
+    /// Hundredths of a chip each player is owed from Classic baccarat commissions
+    pub baccarat_commission_credit: MapView<Owner, u64>,
     
     // ─────────────────────────────────────────────────────────────────────────
     // Player chain state (used when this chain != bank_chain_id from params)
@@ -49,72 +71,113 @@ pub struct ContractsState {
     /// Current active game (if any)
     pub current_game: RegisterView<Option<ActiveGame>>,
     
+    /// Seed commitment the Bank reserved for the next game (if any)
+    pub seed_commitment: RegisterView<Option<String>>,
+    
     /// Game history for UI
     pub game_history: LogView<GameRecord>,
-
-    /// Pending roulette game (player chain - awaiting verification)
-    pub pending_roulette: RegisterView<Option<PendingRouletteGame>>,
 }
 
 // ============================================================================
 // BANK-SIDE TYPES
 // ============================================================================
 
-/// Game pending verification on Bank chain
+/// Game pending on Bank chain until the operator reveals its seed
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PendingGame {
     pub player: Owner,
     pub player_chain: ChainId,
     pub game_type: GameType,
     pub bet: u64,
-    pub seed: u64,
+    /// Commitment to the server seed the game is dealt from
+    pub commitment: String,
+    /// Generator the seed drives (shuffle / spin)
+    pub rng_version: RngVersion,
+    /// Player entropy mixed into the server seed (see `fairness::game_seed`)
+    pub client_seed: Option<ClientSeed>,
     pub created_at: u64,
+    /// When the Bank started waiting on the player or the operator (see `GameTimeouts`)
+    pub waiting_since: u64,
+    /// Every chip escrowed for the game, action stakes included
+    pub staked: u64,
+    /// Worst-case net house loss reserved for this game
+    pub max_liability: u64,
+    /// Blackjack actions accepted so far, the last one possibly awaiting its cards
+    pub actions: Vec<GameAction>,
+    /// Blackjack cards shown so far (none until the operator deals)
+    pub round: Option<ActiveGame>,
+    /// Shoe a card game is dealt from (none for roulette)
+    pub shoe: Option<Shoe>,
+    /// Table of that shoe, held by the game until it settles
+    pub shoe_table: u32,
+    /// Blackjack side-bet stakes, settled on the opening cards
+    pub side_bets: SideBets,
+    /// Bet of each blackjack seat, in seat order (`bet` is their sum)
+    pub seat_bets: Vec<u64>,
+    /// Baccarat main bets (`bet` is their sum)
+    pub baccarat_wagers: Vec<BaccaratWager>,
+    pub baccarat_side_bets: Vec<BaccaratSideBet>,
 }
 
-/// Roulette game pending on Bank chain
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct PendingRouletteBank {
-    pub player: Owner,
-    pub player_chain: ChainId,
-    pub bets: Vec<RouletteBet>,
-    pub seed: u64,
-    pub created_at: u64,
+impl PendingGame {
+    /// Whether the game waits on the operator rather than on a player decision
+    pub fn awaits_operator(&self) -> bool {
+        self.round.as_ref().is_none_or(|round| round.phase != GamePhase::PlayerTurn)
+    }
 }
 
-/// Roulette game pending on Player chain (awaiting result)
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct PendingRouletteGame {
-    pub game_id: u64,
-    pub seed: u64,
-    pub bets: Vec<RouletteBet>,
-    pub outcome: u8,  // Calculated locally
+/// Faucet usage of one player on Bank chain
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct FaucetClaim {
+    pub last_claim_at: u64,
+    pub total_claimed: u64,
+}
+
+impl FaucetClaim {
+    /// Earliest time of the next claim of `amount` chips, or `None` once the
+    /// lifetime cap would be exceeded
+    pub fn next_claim_at(&self, config: &FaucetConfig, amount: u64) -> Option<u64> {
+        if self.total_claimed.saturating_add(amount) > config.lifetime_cap {
+            return None;
+        }
+        if self.total_claimed == 0 {
+            return Some(0);
+        }
+        Some(self.last_claim_at.saturating_add(config.cooldown_micros))
+    }
 }
 
 // ============================================================================
 // PLAYER-SIDE TYPES
 // ============================================================================
 
-/// Active game being played on Player chain
+/// Blackjack round as shown so far: the game being played on Player chain,
+/// and the Bank's copy that revealed cards are checked against
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ActiveGame {
     pub game_id: u64,
-    pub seed: u64,
+    /// Commitment to the server seed (the seed itself is revealed at settlement)
+    pub commitment: String,
     pub bet: u64,
     pub game_type: GameType,
+    /// When the game was dealt on this chain (for the abandon deadline)
+    pub started_at: u64,
     /// Current phase of the game
     pub phase: GamePhase,
     /// Multiple player hands (for split support)
     pub player_hands: Vec<Vec<Card>>,
+    /// Stake and progress of each entry of `player_hands`
+    pub hands: Vec<HandState>,
     /// Index of the hand currently being played
     pub active_hand_index: u32,
-    /// Dealer's visible cards (hole card hidden during PlayerTurn)
+    /// Dealer's visible cards (the hole card is not sent until settlement)
     pub dealer_hand: Vec<Card>,
-    /// Dealer's hole card (revealed on Stand)
-    pub dealer_hole_card: Option<Card>,
-    /// Remaining deck
-    pub deck: Vec<Card>,
-    /// Actions taken (for reporting to Bank)
+    /// Actions whose cards have been revealed
     pub actions: Vec<GameAction>,
+    /// Insurance stake escrowed against a dealer natural (0 if none)
+    pub insurance: u64,
+    /// Side bets settled at the deal (paid when the round settles)
+    pub side_bets: Vec<SideBetResult>,
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Enum)]
@@ -122,6 +185,8 @@ pub enum GamePhase {
     #[default]
     WaitingForGame,
     PlayerTurn,
+    /// Action accepted by the Bank, waiting for the operator to reveal its cards
+    AwaitingCards,
     DealerTurn,
     RoundComplete,
 }
@@ -135,14 +200,38 @@ pub struct GameRecord {
     pub game_id: u64,
     pub game_type: GameType,
     pub player_hands: Vec<Vec<Card>>,
+    /// Per-hand stake, flags and result (blackjack only)
+    pub hands: Vec<HandState>,
+    /// Per-seat bet and result (blackjack only)
+    pub seats: Vec<SeatResult>,
     pub dealer_hand: Vec<Card>,
     pub bet: u64,
     pub result: GameResult,
     pub payout: u64,
     pub timestamp: u64,
+    /// Revealed server seed, its commitment, the generator and the client seed
+    pub seed: Option<String>,
+    pub rng_version: Option<RngVersion>,
+    pub client_seed: Option<ClientSeed>,
+    pub commitment: Option<String>,
+    pub actions: Vec<GameAction>,
     pub roulette_bets: Option<Vec<RouletteBet>>,
     pub roulette_outcome: Option<u8>,
     pub baccarat_winner: Option<contracts::BaccaratBetType>,
     pub baccarat_bet: Option<contracts::BaccaratBetType>,
+    /// Baccarat main bets; `bet` and `payout` are their totals
+    pub baccarat_wagers: Vec<contracts::BaccaratWagerResult>,
+    /// Hundredths of a chip from earlier commissions added to the baccarat payouts
+    pub baccarat_commission_credit: u64,
+    /// Baccarat side bets, paid on top of `payout`
+    pub baccarat_side_bets: Vec<contracts::BaccaratSideBetResult>,
+    /// Shoe a card game was dealt from, needed to replay it
+    pub shoe: Option<Shoe>,
+    /// Blackjack rules the game was dealt under, needed to replay it
+    pub blackjack_rules: Option<BlackjackRules>,
+    /// Baccarat variant the game was paid under, needed to replay it
+    pub baccarat_variant: Option<BaccaratVariant>,
+    /// Blackjack side bets, paid on top of `payout`
+    pub side_bets: Vec<SideBetResult>,
 }
 
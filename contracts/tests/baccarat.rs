@@ -0,0 +1,210 @@
+//! Baccarat engine: scoring, tableau and payouts.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use contracts::games::baccarat::{
+    baccarat_max_liability, baccarat_payout_hundredths, baccarat_side_bet_payout, baccarat_side_bets_max_liability,
+    baccarat_side_bets_total, baccarat_wagers_max_liability, baccarat_wagers_total, baccarat_winner,
+    calculate_baccarat_payout, calculate_baccarat_score, run_baccarat_game, settle_baccarat_side_bets,
+    settle_baccarat_wagers, validate_baccarat_side_bets, validate_baccarat_wagers, MAX_BACCARAT_WAGER,
+};
+use contracts::games::shoe::Shoe;
+use contracts::rng::RngVersion;
+use contracts::{BaccaratBetType, BaccaratSideBet, BaccaratSideBetType, BaccaratVariant, BaccaratWager, Card};
+
+fn cards(values: &[&str]) -> Vec<Card> {
+    values.iter().map(|value| Card::new("hearts", value)).collect()
+}
+
+#[test]
+fn scores_drop_the_tens_digit() {
+    assert_eq!(calculate_baccarat_score(&cards(&["king", "9"])), 9);
+    assert_eq!(calculate_baccarat_score(&cards(&["7", "8"])), 5);
+    assert_eq!(calculate_baccarat_score(&cards(&["ace", "10", "queen"])), 1);
+    assert_eq!(baccarat_winner(4, 4), BaccaratBetType::Tie);
+}
+
+#[test]
+fn coups_follow_the_tableau() {
+    let shoe = Shoe::new(8);
+    for seed in 0..500 {
+        let (winner, player, banker, p_score, b_score) = run_baccarat_game(&shoe, seed, RngVersion::CURRENT);
+        assert_eq!(calculate_baccarat_score(&player), p_score);
+        assert_eq!(calculate_baccarat_score(&banker), b_score);
+        assert_eq!(baccarat_winner(p_score, b_score), winner);
+
+        let p_two = calculate_baccarat_score(&player[..2]);
+        let b_two = calculate_baccarat_score(&banker[..2]);
+        if p_two >= 8 || b_two >= 8 {
+            assert_eq!((player.len(), banker.len()), (2, 2), "naturals stand, seed {seed}");
+        } else {
+            assert_eq!(player.len() == 3, p_two <= 5, "player draws on 0-5, seed {seed}");
+        }
+        assert_eq!(run_baccarat_game(&shoe, seed, RngVersion::CURRENT).1, player);
+    }
+}
+
+#[test]
+fn payouts_include_the_stake() {
+    use BaccaratBetType::*;
+    let classic = BaccaratVariant::Classic;
+    let banker = cards(&["3", "4"]);
+    assert_eq!(calculate_baccarat_payout(classic, Player, Player, &banker, 100), 200);
+    assert_eq!(calculate_baccarat_payout(classic, Banker, Banker, &banker, 100), 195);
+    assert_eq!(calculate_baccarat_payout(classic, Tie, Tie, &banker, 100), 900);
+    assert_eq!(calculate_baccarat_payout(classic, Banker, Tie, &banker, 100), 100);
+    assert_eq!(calculate_baccarat_payout(classic, Player, Banker, &banker, 100), 0);
+    assert_eq!(baccarat_max_liability(classic, 100, Tie), 800);
+}
+
+#[test]
+fn variants_change_only_banker_wins() {
+    use BaccaratBetType::*;
+    let six = cards(&["2", "4"]);
+    let two_card_seven = cards(&["3", "4"]);
+    let dragon_seven = cards(&["2", "king", "5"]);
+
+    // Classic commission is exact in hundredths of a chip
+    assert_eq!(baccarat_payout_hundredths(BaccaratVariant::Classic, Banker, Banker, &six, 10), 1950);
+    assert_eq!(calculate_baccarat_payout(BaccaratVariant::Classic, Banker, Banker, &six, 10), 19);
+    assert_eq!(baccarat_max_liability(BaccaratVariant::Classic, 10, Banker), 10, "a carried credit can round up");
+
+    // Super Six: a winning 6 pays half
+    assert_eq!(calculate_baccarat_payout(BaccaratVariant::SuperSix, Banker, Banker, &six, 10), 15);
+    assert_eq!(calculate_baccarat_payout(BaccaratVariant::SuperSix, Banker, Banker, &two_card_seven, 10), 20);
+
+    // EZ: only a three-card 7 pushes
+    assert_eq!(calculate_baccarat_payout(BaccaratVariant::Ez, Banker, Banker, &dragon_seven, 10), 10);
+    assert_eq!(calculate_baccarat_payout(BaccaratVariant::Ez, Banker, Banker, &two_card_seven, 10), 20);
+    assert_eq!(baccarat_max_liability(BaccaratVariant::Ez, 10, Banker), 10);
+
+    for variant in [BaccaratVariant::Classic, BaccaratVariant::SuperSix, BaccaratVariant::Ez] {
+        assert_eq!(calculate_baccarat_payout(variant, Player, Player, &six, 10), 20);
+        assert_eq!(calculate_baccarat_payout(variant, Tie, Tie, &six, 10), 90);
+        assert_eq!(calculate_baccarat_payout(variant, Banker, Tie, &six, 10), 10);
+    }
+}
+
+#[test]
+fn commission_fractions_are_carried_to_later_wins() {
+    let banker = cards(&["king", "8"]);
+    let wagers = [BaccaratWager { bet_type: BaccaratBetType::Banker, amount: 3 }];
+    let settle = |credit| settle_baccarat_wagers(BaccaratVariant::Classic, &wagers, BaccaratBetType::Banker, &banker, credit);
+
+    // 3 on Banker wins 2.85: 5 chips paid, 0.85 owed
+    let (results, credit) = settle(0);
+    assert_eq!((results[0].payout, credit), (5, 85));
+    // The next win is 5.70 owed: 6 chips paid, 0.70 left
+    let (results, credit) = settle(credit);
+    assert_eq!((results[0].payout, credit), (6, 70));
+    // Twenty wins pay exactly 20 × 5.85
+    let mut paid = 0;
+    let mut credit = 0;
+    for _ in 0..20 {
+        let (results, next) = settle(credit);
+        paid += results[0].payout;
+        credit = next;
+    }
+    assert_eq!((paid, credit), (117, 0));
+
+    // Whole-chip payouts leave the credit alone
+    let player = [BaccaratWager { bet_type: BaccaratBetType::Player, amount: 3 }];
+    let (results, credit) = settle_baccarat_wagers(BaccaratVariant::Classic, &player, BaccaratBetType::Player, &banker, 85);
+    assert_eq!((results[0].payout, credit), (6, 85));
+}
+
+#[test]
+fn several_main_bets_settle_on_one_coup() {
+    use BaccaratBetType::*;
+    let wagers = [BaccaratWager { bet_type: Player, amount: 100 }, BaccaratWager { bet_type: Tie, amount: 10 }];
+    assert!(validate_baccarat_wagers(&wagers).is_ok());
+    assert!(validate_baccarat_wagers(&[]).is_err());
+    assert!(validate_baccarat_wagers(&[wagers[0], wagers[0]]).is_err(), "one bet per type");
+    assert!(validate_baccarat_wagers(&[BaccaratWager { bet_type: Banker, amount: 0 }]).is_err());
+
+    let banker = cards(&["3", "4"]);
+    let payouts = |winner| {
+        let (results, _) = settle_baccarat_wagers(BaccaratVariant::Classic, &wagers, winner, &banker, 0);
+        results.iter().map(|wager| wager.payout).collect::<Vec<_>>()
+    };
+    assert_eq!(payouts(Player), vec![200, 0]);
+    assert_eq!(payouts(Banker), vec![0, 0]);
+    assert_eq!(payouts(Tie), vec![100, 90]);
+
+    // Worst case is the Player win, not the sum of both limits
+    assert_eq!(baccarat_wagers_max_liability(BaccaratVariant::Classic, &wagers), 90);
+    let hedge = [BaccaratWager { bet_type: Player, amount: 100 }, BaccaratWager { bet_type: Banker, amount: 100 }];
+    assert_eq!(baccarat_wagers_max_liability(BaccaratVariant::Classic, &hedge), 0);
+    assert_eq!(baccarat_wagers_max_liability(BaccaratVariant::SuperSix, &hedge), 0);
+}
+
+#[test]
+fn stakes_are_capped_and_never_wrap() {
+    use BaccaratBetType::*;
+    let at_limit = [BaccaratWager { bet_type: Player, amount: MAX_BACCARAT_WAGER }];
+    assert!(validate_baccarat_wagers(&at_limit).is_ok());
+    let over_limit = [BaccaratWager { bet_type: Tie, amount: MAX_BACCARAT_WAGER + 1 }];
+    assert!(validate_baccarat_wagers(&over_limit).is_err());
+
+    // [u64::MAX, 2] would wrap to 1 with unchecked addition
+    let wrapping = [
+        BaccaratWager { bet_type: Player, amount: u64::MAX },
+        BaccaratWager { bet_type: Banker, amount: 2 },
+    ];
+    assert!(validate_baccarat_wagers(&wrapping).is_err());
+    assert!(baccarat_wagers_total(&wrapping).is_err());
+    assert_eq!(baccarat_wagers_total(&at_limit), Ok(MAX_BACCARAT_WAGER));
+    let side_bets = [
+        BaccaratSideBet { bet_type: BaccaratSideBetType::PlayerPair, amount: u64::MAX },
+        BaccaratSideBet { bet_type: BaccaratSideBetType::BankerPair, amount: 2 },
+    ];
+    assert!(baccarat_side_bets_total(&side_bets).is_err());
+}
+
+#[test]
+fn pair_bets_read_the_first_two_cards() {
+    use BaccaratSideBetType::*;
+    let pair = vec![Card::new("hearts", "7"), Card::new("spades", "7"), Card::new("clubs", "2")];
+    let suited_pair = vec![Card::new("hearts", "king"), Card::new("hearts", "king")];
+    let no_pair = cards(&["4", "5"]);
+    assert_eq!(baccarat_side_bet_payout(PlayerPair, 10, &pair, &no_pair), 120);
+    assert_eq!(baccarat_side_bet_payout(BankerPair, 10, &pair, &no_pair), 0);
+    assert_eq!(baccarat_side_bet_payout(EitherPair, 10, &no_pair, &pair), 60);
+    assert_eq!(baccarat_side_bet_payout(PerfectPair, 10, &pair, &no_pair), 0);
+    assert_eq!(baccarat_side_bet_payout(PerfectPair, 10, &no_pair, &suited_pair), 260);
+}
+
+#[test]
+fn dragon_bonus_pays_by_natural_and_margin() {
+    use BaccaratSideBetType::*;
+    // Natural 9 over 7: even money
+    assert_eq!(baccarat_side_bet_payout(DragonPlayer, 10, &cards(&["4", "5"]), &cards(&["3", "4"])), 20);
+    // Natural tie pushes
+    assert_eq!(baccarat_side_bet_payout(DragonBanker, 10, &cards(&["ace", "7"]), &cards(&["3", "5"])), 10);
+    // Three-card 9 over 0 wins by nine: 30:1
+    assert_eq!(baccarat_side_bet_payout(DragonPlayer, 10, &cards(&["2", "3", "4"]), &cards(&["king", "10", "queen"])), 310);
+    // Win by four: 1:1, by three: lost
+    assert_eq!(baccarat_side_bet_payout(DragonBanker, 10, &cards(&["ace", "2", "king"]), &cards(&["king", "2", "5"])), 20);
+    assert_eq!(baccarat_side_bet_payout(DragonBanker, 10, &cards(&["ace", "2", "king"]), &cards(&["king", "2", "4"])), 0);
+    assert_eq!(baccarat_side_bet_payout(DragonPlayer, 10, &cards(&["king", "2", "4"]), &cards(&["ace", "2", "king"])), 0);
+}
+
+#[test]
+fn side_bets_are_checked_and_itemised() {
+    let side_bets = [
+        BaccaratSideBet { bet_type: BaccaratSideBetType::PlayerPair, amount: 5 },
+        BaccaratSideBet { bet_type: BaccaratSideBetType::DragonBanker, amount: 10 },
+    ];
+    assert!(validate_baccarat_side_bets(&side_bets, 10).is_ok());
+    assert!(validate_baccarat_side_bets(&side_bets, 5).is_err(), "no side bet above the main bet");
+    assert!(validate_baccarat_side_bets(&[side_bets[0], side_bets[0]], 10).is_err());
+    let empty = BaccaratSideBet { bet_type: BaccaratSideBetType::EitherPair, amount: 0 };
+    assert!(validate_baccarat_side_bets(&[empty], 10).is_err());
+
+    let results = settle_baccarat_side_bets(&side_bets, &cards(&["8", "8"]), &cards(&["2", "3", "king"]));
+    assert_eq!(results.iter().map(|result| (result.bet_type, result.payout)).collect::<Vec<_>>(), vec![
+        (BaccaratSideBetType::PlayerPair, 60),
+        (BaccaratSideBetType::DragonBanker, 0),
+    ]);
+    assert_eq!(baccarat_side_bets_max_liability(&side_bets), 5 * 11 + 10 * 30);
+}
@@ -0,0 +1,622 @@
+//! Blackjack engine: hand values, action application and seeded replays.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::collections::HashSet;
+
+use contracts::games::blackjack::{
+    apply_blackjack_action, available_actions, blackjack_max_liability, calculate_hand_value, card_value,
+    next_reveal, perfect_pairs, replay_and_verify, replay_seats, seat_hands, seat_results, settle_side_bets, settles_at_deal,
+    side_bets_max_liability, twenty_one_plus_three, validate_seat_bets, BlackjackTable, Reveal, ALLOWED_BETS,
+    MAX_BLACKJACK_HANDS,
+};
+use contracts::rng::RngVersion;
+use contracts::games::create_decks;
+use contracts::games::shoe::Shoe;
+use contracts::{
+    BlackjackPayout, BlackjackRules, Card, GameAction, GameResult, HandState, SideBetKind, SideBetOutcome, SideBets,
+    SurrenderRule,
+};
+
+fn cards(values: &[&str]) -> Vec<Card> {
+    values.iter().map(|value| Card::new("spades", value)).collect()
+}
+
+/// Hand states of one seat's hands at a bet of 2 (several hands came from a split)
+fn states(hands: &[Vec<Card>]) -> Vec<HandState> {
+    hands.iter()
+        .map(|_| HandState { from_split: hands.len() > 1, ..HandState::new(2) })
+        .collect()
+}
+
+fn deal(seed: u64, bet: u64) -> BlackjackTable {
+    let rules = BlackjackRules::default();
+    BlackjackTable::deal(&Shoe::new(rules.decks), seed, RngVersion::CURRENT, bet, rules)
+}
+
+fn replay(seed: u64, bet: u64, actions: &[GameAction]) -> BlackjackTable {
+    let rules = BlackjackRules::default();
+    replay_and_verify(&Shoe::new(rules.decks), seed, RngVersion::CURRENT, bet, rules, actions)
+}
+
+/// First seed whose deal satisfies `wanted`
+fn seed_where(wanted: impl Fn(&BlackjackTable) -> bool) -> u64 {
+    (0..).find(|&seed| wanted(&deal(seed, 2))).unwrap()
+}
+
+fn dealer_ace(table: &BlackjackTable) -> bool {
+    table.dealer_hand[0].value == "ace"
+}
+
+fn dealer_natural(table: &BlackjackTable) -> bool {
+    calculate_hand_value(&table.dealer_hand) == 21
+}
+
+fn player_natural(table: &BlackjackTable) -> bool {
+    calculate_hand_value(&table.player_hands[0]) == 21
+}
+
+#[test]
+fn aces_count_one_or_eleven() {
+    assert_eq!(calculate_hand_value(&cards(&["ace", "king"])), 21);
+    assert_eq!(calculate_hand_value(&cards(&["ace", "ace"])), 12);
+    assert_eq!(calculate_hand_value(&cards(&["ace", "6", "9"])), 16);
+    assert_eq!(calculate_hand_value(&cards(&["queen", "jack", "2"])), 22);
+}
+
+#[test]
+fn actions_move_through_the_hands() {
+    let rules = BlackjackRules::default();
+    let mut hands = vec![cards(&["8", "8"])];
+    let mut states = vec![HandState::new(2)];
+    let mut deck = cards(&["10", "3", "king", "2"]);
+    let mut draw = || deck.pop().unwrap();
+
+    // Split keeps playing the first hand, each half receiving a new card
+    let idx = apply_blackjack_action(&mut hands, &mut states, 0, GameAction::Split, &rules, &mut draw);
+    assert_eq!(idx, 0);
+    assert_eq!(hands, vec![cards(&["8", "2"]), cards(&["8", "king"])]);
+    assert!(states.iter().all(|state| state.from_split && state.bet == 2));
+
+    // Double takes exactly one card, doubles that hand's bet and moves on
+    let idx = apply_blackjack_action(&mut hands, &mut states, idx, GameAction::DoubleDown, &rules, &mut draw);
+    assert_eq!(idx, 1);
+    assert_eq!(calculate_hand_value(&hands[0]), 13);
+    assert!(states[0].doubled && states[0].finished);
+    assert_eq!((states[0].bet, states[1].bet), (4, 2));
+
+    // A bust ends the hand
+    let idx = apply_blackjack_action(&mut hands, &mut states, idx, GameAction::Hit, &rules, &mut draw);
+    assert_eq!(idx, 2);
+    assert!(calculate_hand_value(&hands[1]) > 21);
+    assert!(states[1].finished);
+}
+
+#[test]
+#[should_panic(expected = "Split is not allowed on hand 0")]
+fn split_needs_a_pair() {
+    let mut hands = vec![cards(&["8", "9"])];
+    let mut states = vec![HandState::new(2)];
+    let mut deck = cards(&["2", "3"]);
+    apply_blackjack_action(&mut hands, &mut states, 0, GameAction::Split, &BlackjackRules::default(), || deck.pop().unwrap());
+}
+
+#[test]
+#[should_panic(expected = "Hand 0 is already finished")]
+fn no_split_after_a_double() {
+    let rules = BlackjackRules::default();
+    let mut hands = vec![cards(&["4", "4"])];
+    let mut states = vec![HandState::new(2)];
+    let mut deck = cards(&["2", "3", "4"]);
+    let mut draw = || deck.pop().unwrap();
+    apply_blackjack_action(&mut hands, &mut states, 0, GameAction::DoubleDown, &rules, &mut draw);
+    apply_blackjack_action(&mut hands, &mut states, 0, GameAction::Split, &rules, &mut draw);
+}
+
+#[test]
+#[should_panic(expected = "Split is not allowed")]
+fn bank_replay_rejects_an_illegal_split() {
+    let seed = seed_where(|t| {
+        !t.has_natural() && !dealer_ace(t)
+            && calculate_hand_value(&t.player_hands[0][..1]) != calculate_hand_value(&t.player_hands[0][1..])
+    });
+    replay(seed, 2, &[GameAction::Split]);
+}
+
+#[test]
+fn split_hands_settle_one_by_one() {
+    let seed = seed_where(|t| {
+        let hand = &t.player_hands[0];
+        !t.has_natural() && !dealer_ace(t) && hand[0].value == hand[1].value && hand[0].value != "ace"
+    });
+    let table = replay(seed, 2, &[GameAction::Split, GameAction::Stand, GameAction::Stand]);
+    assert!(table.is_finished());
+    assert_eq!(table.total_bet(), 4);
+    let (_, payout, player_hands, hands, _) = table.settle();
+    assert_eq!(player_hands.len(), 2);
+    assert_eq!(payout, hands.iter().map(|hand| hand.payout).sum::<u64>());
+    for hand in &hands {
+        assert!(hand.from_split && hand.finished && !hand.doubled);
+        let expected = match hand.result.unwrap() {
+            GameResult::PlayerWin | GameResult::DealerBust => 4,
+            GameResult::Push => 2,
+            GameResult::DealerWin | GameResult::PlayerBust => 0,
+            other => panic!("unexpected hand result {other:?}"),
+        };
+        assert_eq!(hand.payout, expected);
+    }
+}
+
+#[test]
+fn replay_is_deterministic_and_deals_distinct_cards() {
+    for seed in 0..50 {
+        let a = replay(seed, 2, &[GameAction::Stand]);
+        let b = replay(seed, 2, &[GameAction::Stand]);
+        assert!(a.is_finished());
+        let (result_a, payout_a, hands_a, states_a, dealer_a) = a.settle();
+        assert_eq!((result_a, payout_a, hands_a.clone(), states_a, dealer_a.clone()), b.settle());
+
+        let dealt: HashSet<_> = hands_a.iter().flatten().chain(&dealer_a)
+            .map(|card| (card.suit.clone(), card.value.clone()))
+            .collect();
+        assert_eq!(dealt.len(), hands_a.iter().map(Vec::len).sum::<usize>() + dealer_a.len());
+    }
+}
+
+#[test]
+fn settlement_pays_by_result() {
+    for seed in 0..200 {
+        let table = replay(seed, 2, &[GameAction::Stand]);
+        let natural = table.has_natural();
+        let (result, payout, ..) = table.settle();
+        let expected = match result {
+            GameResult::PlayerBlackjack => 5,
+            GameResult::PlayerWin => 4,
+            GameResult::Push => 2,
+            _ => 0,
+        };
+        assert_eq!(payout, expected, "seed {seed}");
+        assert!(natural || result != GameResult::PlayerBlackjack);
+    }
+}
+
+#[test]
+fn actions_after_the_round_are_ignored() {
+    let seed = (0..)
+        .find(|&seed| !deal(seed, 1).has_natural())
+        .unwrap();
+    let once = replay(seed, 1, &[GameAction::Stand]);
+    let again = replay(seed, 1, &[GameAction::Stand, GameAction::Hit]);
+    assert_eq!(once.player_hands, again.player_hands);
+    assert_eq!(once.total_bet(), again.total_bet());
+}
+
+#[test]
+fn rounds_over_at_the_deal_are_settled_without_showing_cards() {
+    let rule_sets = [
+        BlackjackRules::default(),
+        BlackjackRules { dealer_peek: false, ..Default::default() },
+        BlackjackRules { surrender: SurrenderRule::Early, ..Default::default() },
+    ];
+    for rules in rule_sets {
+        // (player natural, dealer natural, dealer ace up) classes seen, and how many ended at the deal
+        let mut classes = HashSet::new();
+        let mut settled = 0;
+        for seed in 0..100_000 {
+            let shoe = Shoe::new(rules.decks);
+            let table = BlackjackTable::deal(&shoe, seed, RngVersion::CURRENT, 2, rules);
+            assert_eq!(
+                settles_at_deal(&table.player_hands, &table.dealer_hand, &rules),
+                table.is_finished(),
+                "seed {seed} under {rules:?}"
+            );
+            // The operator reveals the seed of a finished round, and otherwise
+            // deals the hand and the up-card alone, which the Bank accepts
+            let reveal = next_reveal(&shoe, seed, RngVersion::CURRENT, &[2], rules, &[], None);
+            if table.is_finished() {
+                assert_eq!(reveal, Some(Reveal::Seed), "seed {seed}");
+            } else {
+                let mut cards = table.player_hands[0].clone();
+                cards.push(table.dealer_hand[0].clone());
+                assert_eq!(reveal, Some(Reveal::Cards(cards)), "seed {seed}");
+                assert!(!settles_at_deal(&table.player_hands, &table.dealer_hand[..1], &rules), "seed {seed}");
+            }
+
+            classes.insert((player_natural(&table), dealer_natural(&table), dealer_ace(&table)));
+            settled += usize::from(table.is_finished());
+            if seed >= 2000 && classes.len() == 8 {
+                break;
+            }
+        }
+        assert_eq!(classes.len(), 8, "every seed class is covered under {rules:?}");
+        assert!(settled > 0);
+    }
+}
+
+#[test]
+fn liability_covers_every_hand_doubled() {
+    assert_eq!(blackjack_max_liability(5, &BlackjackRules::default()), 5 * 2 * MAX_BLACKJACK_HANDS as u64);
+    let no_resplit = BlackjackRules { max_split_hands: 2, ..Default::default() };
+    assert_eq!(blackjack_max_liability(5, &no_resplit), 5 * 2 * 2);
+}
+
+#[test]
+fn insurance_pays_two_to_one_on_a_dealer_natural() {
+    let seed = seed_where(|t| dealer_ace(t) && dealer_natural(t) && !player_natural(t));
+    let table = deal(seed, 2);
+    // The dealer only peeks once the insurance decision is made
+    assert!(table.insurance_offered() && !table.is_finished());
+
+    let table = replay(seed, 2, &[GameAction::Insurance]);
+    assert!(table.is_finished());
+    assert_eq!(table.total_bet(), 3);
+    let (result, payout, ..) = table.settle();
+    assert_eq!((result, payout), (GameResult::DealerWin, 3));
+}
+
+#[test]
+fn insurance_is_lost_without_a_dealer_natural() {
+    let seed = seed_where(|t| dealer_ace(t) && !dealer_natural(t) && !player_natural(t));
+    let insured = replay(seed, 2, &[GameAction::Insurance, GameAction::Stand]);
+    let declined = replay(seed, 2, &[GameAction::Stand]);
+    assert_eq!(insured.total_bet(), declined.total_bet() + 1);
+    assert_eq!(insured.settle().1, declined.settle().1);
+}
+
+#[test]
+fn even_money_pays_one_to_one_on_a_natural() {
+    for dealer_has_natural in [false, true] {
+        let seed = seed_where(|t| dealer_ace(t) && player_natural(t) && dealer_natural(t) == dealer_has_natural);
+        let table = replay(seed, 2, &[GameAction::EvenMoney]);
+        assert!(table.is_finished());
+        let (result, payout, ..) = table.settle();
+        assert_eq!((result, payout), (GameResult::PlayerWin, 4));
+    }
+}
+
+#[test]
+fn insurance_is_only_offered_against_an_ace() {
+    let rules = BlackjackRules::default();
+    let hand = vec![cards(&["10", "6"])];
+    let offered = |up: &str, taken: &[GameAction]| {
+        available_actions(&hand, &states(&hand), 0, &Card::new("hearts", up), taken, &rules)
+    };
+    assert!(offered("ace", &[]).contains(&GameAction::Insurance));
+    assert!(!offered("ace", &[GameAction::Hit]).contains(&GameAction::Insurance));
+    assert!(!offered("king", &[]).contains(&GameAction::Insurance));
+
+    let natural = vec![cards(&["ace", "king"])];
+    let actions = available_actions(&natural, &states(&natural), 0, &Card::new("hearts", "ace"), &[], &rules);
+    assert!(actions.contains(&GameAction::EvenMoney) && !actions.contains(&GameAction::Insurance));
+}
+
+#[test]
+fn surrender_returns_half_the_bet() {
+    let seed = seed_where(|t| !t.has_natural() && !t.insurance_offered());
+    let table = replay(seed, 4, &[GameAction::Surrender]);
+    assert!(table.is_finished());
+    let (result, payout, ..) = table.settle();
+    assert_eq!((result, payout), (GameResult::Surrender, 2));
+}
+
+#[test]
+fn surrender_needs_an_even_bet() {
+    let hand = vec![cards(&["10", "6"])];
+    let up = Card::new("hearts", "10");
+    let offered = |bet: u64| {
+        available_actions(&hand, &[HandState::new(bet)], 0, &up, &[], &BlackjackRules::default())
+            .contains(&GameAction::Surrender)
+    };
+    assert!(offered(2) && offered(4));
+    assert!(!offered(1) && !offered(3) && !offered(5));
+}
+
+#[test]
+#[should_panic(expected = "Bet cannot be halved to surrender")]
+fn bank_replay_rejects_surrendering_an_odd_bet() {
+    let seed = seed_where(|t| !t.has_natural() && !t.insurance_offered());
+    replay(seed, 3, &[GameAction::Surrender]);
+}
+
+#[test]
+fn late_surrender_comes_after_the_peek_and_early_before_it() {
+    let late = BlackjackRules { surrender: SurrenderRule::Late, ..Default::default() };
+    let early = BlackjackRules { surrender: SurrenderRule::Early, ..Default::default() };
+    for up in ["ace", "king"] {
+        let seed = seed_where(|t| t.dealer_hand[0].value == up && dealer_natural(t) && !player_natural(t));
+        let settle = |rules: BlackjackRules| replay_and_verify(&Shoe::new(rules.decks), seed, RngVersion::CURRENT, 2, rules, &[GameAction::Surrender]).settle();
+        assert_eq!(settle(late).0, GameResult::DealerWin, "{up}");
+        assert_eq!(settle(early).0, GameResult::Surrender, "{up}");
+    }
+}
+
+#[test]
+#[should_panic(expected = "Surrender is only allowed as the first decision")]
+fn surrender_is_only_the_first_decision() {
+    let seed = (0..)
+        .find(|&seed| !deal(seed, 2).has_natural() && !replay(seed, 2, &[GameAction::Hit]).is_finished())
+        .unwrap();
+    replay(seed, 2, &[GameAction::Hit, GameAction::Surrender]);
+}
+
+#[test]
+fn surrender_can_be_ruled_out() {
+    let hand = vec![cards(&["10", "6"])];
+    let up = Card::new("hearts", "9");
+    let offered = |surrender| {
+        available_actions(&hand, &states(&hand), 0, &up, &[], &BlackjackRules { surrender, ..Default::default() })
+            .contains(&GameAction::Surrender)
+    };
+    assert!(offered(SurrenderRule::Late) && offered(SurrenderRule::Early));
+    assert!(!offered(SurrenderRule::NotAllowed));
+}
+
+#[test]
+fn natural_pays_by_the_house_payout() {
+    let seed = seed_where(|t| player_natural(t) && !dealer_natural(t) && !dealer_ace(t));
+    let settle = |blackjack_payout, bet| {
+        let rules = BlackjackRules { blackjack_payout, ..Default::default() };
+        replay_and_verify(&Shoe::new(rules.decks), seed, RngVersion::CURRENT, bet, rules, &[GameAction::Stand]).settle()
+    };
+    assert_eq!(settle(BlackjackPayout::ThreeToTwo, 10).1, 10 + 15);
+    assert_eq!(settle(BlackjackPayout::SixToFive, 10).1, 10 + 12);
+    // Every allowed bet wins exactly 3/2 or 6/5 of itself
+    for bet in ALLOWED_BETS {
+        assert_eq!(settle(BlackjackPayout::ThreeToTwo, bet).1 * 2, bet * 5, "3:2 on {bet}");
+        assert_eq!(settle(BlackjackPayout::SixToFive, bet).1 * 5, bet * 11, "6:5 on {bet}");
+    }
+}
+
+#[test]
+fn bets_must_pay_a_natural_in_whole_chips() {
+    let three_to_two = BlackjackRules::default();
+    let six_to_five = BlackjackRules { blackjack_payout: BlackjackPayout::SixToFive, ..Default::default() };
+    for rules in [three_to_two, six_to_five] {
+        assert!(validate_seat_bets(&ALLOWED_BETS[..1], &ALLOWED_BETS, &rules).is_ok());
+    }
+    let allowed = [1, 2, 5];
+    assert!(validate_seat_bets(&[1], &allowed, &three_to_two).is_err());
+    assert!(validate_seat_bets(&[2], &allowed, &three_to_two).is_ok());
+    assert!(validate_seat_bets(&[2], &allowed, &six_to_five).is_err());
+    assert!(validate_seat_bets(&[5], &allowed, &six_to_five).is_ok());
+}
+
+#[test]
+fn dealer_hits_soft_17_only_under_h17() {
+    let seed = seed_where(|t| {
+        !t.has_natural() && !dealer_ace(t) && calculate_hand_value(&t.dealer_hand) == 17
+            && t.dealer_hand.iter().any(|card| card.value == "ace")
+    });
+    let dealer_cards = |dealer_hits_soft_17| {
+        let rules = BlackjackRules { dealer_hits_soft_17, ..Default::default() };
+        replay_and_verify(&Shoe::new(rules.decks), seed, RngVersion::CURRENT, 2, rules, &[GameAction::Stand]).settle().4.len()
+    };
+    assert_eq!(dealer_cards(false), 2);
+    assert!(dealer_cards(true) > 2);
+}
+
+#[test]
+fn shoe_holds_every_deck() {
+    let shoe = create_decks(6);
+    assert_eq!(shoe.len(), 6 * 52);
+    assert_eq!(shoe.iter().filter(|card| card.id == "ace_of_spades").count(), 6);
+
+    let rules = BlackjackRules { decks: 6, ..Default::default() };
+    let a = replay_and_verify(&Shoe::new(rules.decks), 7, RngVersion::CURRENT, 2, rules, &[GameAction::Stand]).settle();
+    let b = replay_and_verify(&Shoe::new(rules.decks), 7, RngVersion::CURRENT, 2, rules, &[GameAction::Stand]).settle();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn split_and_double_follow_the_rules() {
+    let pair = vec![cards(&["8", "8"])];
+    let split_hands = vec![cards(&["8", "3"]), cards(&["8", "2"])];
+    let up = Card::new("hearts", "6");
+    let offered = |hands: &[Vec<Card>], rules: BlackjackRules| {
+        available_actions(hands, &states(hands), 0, &up, &[GameAction::Split], &rules)
+    };
+
+    let no_splits = BlackjackRules { max_split_hands: 1, ..Default::default() };
+    assert!(!available_actions(&pair, &states(&pair), 0, &up, &[], &no_splits).contains(&GameAction::Split));
+    assert!(offered(&split_hands, BlackjackRules::default()).contains(&GameAction::DoubleDown));
+    let no_das = BlackjackRules { double_after_split: false, ..Default::default() };
+    assert!(!offered(&split_hands, no_das).contains(&GameAction::DoubleDown));
+}
+
+#[test]
+fn split_aces_take_one_card() {
+    let rules = BlackjackRules { split_aces_one_card: true, ..Default::default() };
+    let mut hands = vec![cards(&["ace", "ace"])];
+    let mut states = vec![HandState::new(2)];
+    let mut deck = cards(&["5", "king"]);
+    let next = apply_blackjack_action(&mut hands, &mut states, 0, GameAction::Split, &rules, || deck.pop().unwrap());
+    assert_eq!(next, hands.len(), "both halves are done");
+    // A ten on a split ace is 21, not a natural
+    assert_eq!(calculate_hand_value(&hands[0]), 21);
+}
+
+#[test]
+fn without_a_peek_a_dealer_natural_takes_every_stake() {
+    let seed = seed_where(|t| dealer_natural(t) && !dealer_ace(t) && !player_natural(t));
+    let rules = BlackjackRules { dealer_peek: false, ..Default::default() };
+    assert!(!BlackjackTable::deal(&Shoe::new(rules.decks), seed, RngVersion::CURRENT, 2, rules).is_finished());
+
+    let table = replay_and_verify(&Shoe::new(rules.decks), seed, RngVersion::CURRENT, 2, rules, &[GameAction::DoubleDown]);
+    assert_eq!(table.total_bet(), 4);
+    let (result, payout, ..) = table.settle();
+    assert_eq!((result, payout), (GameResult::DealerWin, 0));
+}
+
+#[test]
+fn perfect_pairs_grades_the_pair() {
+    let pair = |a: (&str, &str), b: (&str, &str)| perfect_pairs(&Card::new(a.0, a.1), &Card::new(b.0, b.1));
+    assert_eq!(pair(("spades", "8"), ("spades", "8")), Some(SideBetOutcome::PerfectPair));
+    assert_eq!(pair(("spades", "8"), ("clubs", "8")), Some(SideBetOutcome::ColouredPair));
+    assert_eq!(pair(("spades", "8"), ("hearts", "8")), Some(SideBetOutcome::MixedPair));
+    // Tens are not a pair by value alone
+    assert_eq!(pair(("spades", "10"), ("spades", "king")), None);
+}
+
+#[test]
+fn twenty_one_plus_three_reads_the_poker_hand() {
+    let hand = |cards: [(&str, &str); 3]| {
+        let [a, b, up] = cards.map(|(suit, value)| Card::new(suit, value));
+        twenty_one_plus_three(&a, &b, &up)
+    };
+    assert_eq!(hand([("hearts", "9"), ("hearts", "9"), ("hearts", "9")]), Some(SideBetOutcome::SuitedTrips));
+    assert_eq!(hand([("hearts", "9"), ("hearts", "10"), ("hearts", "jack")]), Some(SideBetOutcome::StraightFlush));
+    assert_eq!(hand([("hearts", "9"), ("clubs", "9"), ("hearts", "9")]), Some(SideBetOutcome::ThreeOfAKind));
+    assert_eq!(hand([("hearts", "queen"), ("clubs", "king"), ("hearts", "ace")]), Some(SideBetOutcome::Straight));
+    assert_eq!(hand([("hearts", "ace"), ("clubs", "2"), ("hearts", "3")]), Some(SideBetOutcome::Straight));
+    assert_eq!(hand([("hearts", "2"), ("hearts", "7"), ("hearts", "king")]), Some(SideBetOutcome::Flush));
+    // No wrap-around straight
+    assert_eq!(hand([("hearts", "king"), ("clubs", "ace"), ("hearts", "2")]), None);
+}
+
+#[test]
+fn side_bets_pay_only_what_was_staked() {
+    let hand = vec![Card::new("spades", "8"), Card::new("spades", "8")];
+    let up = Card::new("spades", "9");
+    let results = settle_side_bets(&SideBets { perfect_pairs: 2, twenty_one_plus_three: 0 }, &hand, &up);
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].kind, SideBetKind::PerfectPairs);
+    assert_eq!(results[0].payout, 2 + 2 * 25);
+
+    let side_bets = SideBets { perfect_pairs: 2, twenty_one_plus_three: 3 };
+    let hand = cards(&["4", "8"]);
+    let results = settle_side_bets(&side_bets, &hand, &Card::new("hearts", "9"));
+    assert!(results.iter().all(|side_bet| side_bet.outcome.is_none() && side_bet.payout == 0));
+    assert_eq!(side_bets_max_liability(&side_bets), 2 * 25 + 3 * 100);
+}
+
+fn deal_seats(seed: u64, bets: &[u64]) -> BlackjackTable {
+    let rules = BlackjackRules::default();
+    BlackjackTable::deal_seats(&Shoe::new(rules.decks), seed, RngVersion::CURRENT, bets, rules)
+}
+
+#[test]
+fn seats_are_dealt_from_one_shuffle() {
+    let single = deal(11, 2);
+    let table = deal_seats(11, &[2, 3, 4]);
+    assert_eq!(table.player_hands[0], single.player_hands[0], "the first seat takes the first two cards");
+    assert_eq!(table.player_hands.len(), 3);
+    assert_eq!(table.hands.iter().map(|hand| (hand.seat, hand.bet)).collect::<Vec<_>>(), vec![(0, 2), (1, 3), (2, 4)]);
+    let mut ids: Vec<&str> = table.player_hands.iter().flatten().chain(&table.dealer_hand)
+        .map(|card| card.id.as_str())
+        .collect();
+    ids.sort_unstable();
+    ids.dedup();
+    assert_eq!(ids.len(), 8, "a single-deck round deals every card once");
+}
+
+#[test]
+fn seats_are_played_in_order_and_split_hands_stay_on_their_seat() {
+    let rules = BlackjackRules::default();
+    let mut hands = vec![cards(&["8", "8"]), cards(&["10", "6"])];
+    let mut states = seat_hands(&hands, &[2, 3]);
+    let mut deck = cards(&["4", "9"]);
+    let next = apply_blackjack_action(&mut hands, &mut states, 0, GameAction::Split, &rules, || deck.pop().unwrap());
+    assert_eq!(next, 0);
+    assert_eq!((states[2].seat, states[2].bet), (0, 2));
+    let next = apply_blackjack_action(&mut hands, &mut states, next, GameAction::Stand, &rules, || unreachable!());
+    assert_eq!(next, 2, "the split hand is played before the next seat");
+    let next = apply_blackjack_action(&mut hands, &mut states, next, GameAction::Stand, &rules, || unreachable!());
+    assert_eq!(next, 1);
+    let next = apply_blackjack_action(&mut hands, &mut states, next, GameAction::Stand, &rules, || unreachable!());
+    assert_eq!(next, hands.len());
+}
+
+#[test]
+fn several_seats_get_no_insurance_or_surrender() {
+    let hands = vec![cards(&["10", "6"]), cards(&["9", "7"])];
+    let states = seat_hands(&hands, &[2, 2]);
+    let actions = available_actions(&hands, &states, 0, &Card::new("hearts", "ace"), &[], &BlackjackRules::default());
+    assert_eq!(actions, vec![GameAction::Hit, GameAction::Stand, GameAction::DoubleDown]);
+}
+
+#[test]
+fn seat_naturals_stand_and_are_paid_at_settlement() {
+    let rules = BlackjackRules::default();
+    let bets = [2, 4];
+    let seed = (0..)
+        .find(|&seed| {
+            let table = deal_seats(seed, &bets);
+            table.hands[1].finished && !table.hands[0].finished && !dealer_natural(&table)
+        })
+        .unwrap();
+    let table = deal_seats(seed, &bets);
+    assert_eq!(table.active_idx, 0);
+    let table = replay_seats(&Shoe::new(rules.decks), seed, RngVersion::CURRENT, &bets, rules, &[GameAction::Stand]);
+    assert!(table.is_finished());
+    let (_, payout, _, hands, _) = table.settle();
+    assert_eq!((hands[1].result, hands[1].payout), (Some(GameResult::PlayerBlackjack), 4 + 6));
+
+    let seats = seat_results(&hands, &bets);
+    assert_eq!(seats.len(), 2);
+    assert_eq!(seats.iter().map(|seat| seat.payout).sum::<u64>(), payout);
+    assert_eq!((seats[1].bet, seats[1].result), (4, GameResult::PlayerBlackjack));
+}
+
+#[test]
+fn settles_at_deal_agrees_with_the_table_on_several_seats() {
+    for seed in 0..2000 {
+        let table = deal_seats(seed, &[1, 2, 3]);
+        assert_eq!(settles_at_deal(&table.player_hands, &table.dealer_hand, &table.rules), table.is_finished(), "seed {seed}");
+    }
+}
+
+#[test]
+fn operator_reveals_the_cards_of_each_action_until_the_round_is_over() {
+    let shoe = Shoe::new(1);
+    let rules = BlackjackRules::default();
+    let seed = (0..1000)
+        .find(|&seed| {
+            let table = BlackjackTable::deal(&shoe, seed, RngVersion::CURRENT, 10, rules);
+            !table.is_finished() && !table.insurance_offered()
+                && !replay_and_verify(&shoe, seed, RngVersion::CURRENT, 10, rules, &[GameAction::Hit]).is_finished()
+        })
+        .expect("an open round that survives a hit");
+    let actions = [GameAction::Hit, GameAction::Stand];
+    let reveal = |revealed| next_reveal(&shoe, seed, RngVersion::CURRENT, &[10], rules, &actions[..revealed + 1], Some(revealed));
+    let hit = replay_and_verify(&shoe, seed, RngVersion::CURRENT, 10, rules, &actions[..1]);
+    assert_eq!(reveal(0), Some(Reveal::Cards(vec![hit.player_hands[0][2].clone()])));
+    // Standing on the only hand ends the round, so the seed comes next
+    assert_eq!(reveal(1), Some(Reveal::Seed));
+    // Nothing is owed while the player has not acted
+    assert_eq!(next_reveal(&shoe, seed, RngVersion::CURRENT, &[10], rules, &actions[..1], Some(1)), None);
+}
+
+#[test]
+fn a_short_shoe_deals_on_from_its_discards() {
+    // Only the twenty low cards are left, far fewer than three seats hitting to 17 need
+    let mut shoe = Shoe::new(1);
+    let high: Vec<Card> = shoe.cards().into_iter().filter(|card| !(2..=6).contains(&card_value(card))).collect();
+    shoe.remove(&high).unwrap();
+    assert_eq!(shoe.cards_left(), 20);
+    let mut reshuffles = 0;
+    for seed in 0..50 {
+        let mut table = BlackjackTable::deal_seats(&shoe, seed, RngVersion::CURRENT, &[10, 10, 10], BlackjackRules::default());
+        while !table.is_finished() {
+            let value = calculate_hand_value(&table.player_hands[table.active_idx]);
+            table.apply(if value < 17 { GameAction::Hit } else { GameAction::Stand });
+        }
+        table.play_dealer();
+        let reshuffled = table.reshuffled_mid_round();
+        let (_, _, player_hands, _, dealer_hand) = table.settle();
+        let dealt: Vec<Card> = player_hands.concat().into_iter().chain(dealer_hand).collect();
+        // No card comes out twice, and only a reshuffle brings back high cards
+        assert!(Shoe::new(1).remove(&dealt).is_ok(), "seed {seed}");
+        assert_eq!(reshuffled, shoe.clone().remove(&dealt).is_err(), "seed {seed}");
+        reshuffles += reshuffled as u32;
+    }
+    assert!(reshuffles > 0);
+}
+
+#[test]
+fn seat_bets_are_checked_against_the_table() {
+    let rules = BlackjackRules { max_seats: 2, ..Default::default() };
+    assert!(validate_seat_bets(&[10, 50], &ALLOWED_BETS, &rules).is_ok());
+    assert!(validate_seat_bets(&[10, 10, 10], &ALLOWED_BETS, &rules).is_err());
+    assert!(validate_seat_bets(&[], &ALLOWED_BETS, &rules).is_err());
+    assert!(validate_seat_bets(&[60], &ALLOWED_BETS, &rules).is_err());
+}
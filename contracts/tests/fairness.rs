@@ -0,0 +1,44 @@
+//! Game seed derivation and commitment checks.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use contracts::fairness::{game_seed, is_hex_32_bytes, seed_commitment, verify_seed_commitment, ClientSeed};
+
+fn server_seed(byte: u8) -> String {
+    format!("{byte:02x}").repeat(32)
+}
+
+#[test]
+fn seed_encodings_are_pinned() {
+    // Recomputable from the documented encodings with any SHA-256 tool
+    let client = ClientSeed { seed: "lucky".to_string(), nonce: 3 };
+    assert_eq!(game_seed(&server_seed(0), Some(&client)), 13586238313921888138);
+    assert_eq!(
+        seed_commitment(&server_seed(0)),
+        "ac8510e9e1cffdf19e367201e1c4fae17982a9a06983bb44a8ad8c32663b8a6f"
+    );
+}
+
+#[test]
+fn commitment_matches_revealed_seed() {
+    let commitment = seed_commitment(&server_seed(7));
+    assert!(is_hex_32_bytes(&commitment));
+    assert!(verify_seed_commitment(&commitment, &server_seed(7)));
+    assert!(!verify_seed_commitment(&commitment, &server_seed(8)));
+
+    assert!(is_hex_32_bytes(&server_seed(0xab)));
+    assert!(!is_hex_32_bytes(&server_seed(0xab).to_uppercase()));
+    assert!(!is_hex_32_bytes(&server_seed(0xab)[1..]));
+}
+
+#[test]
+fn client_seed_changes_the_game_seed() {
+    assert_eq!(ClientSeed::from_parts(None, Some(3)), None);
+
+    let client = ClientSeed::from_parts(Some("lucky".to_string()), None).expect("seed given");
+    assert_eq!(client.nonce, 0);
+    let mixed = game_seed(&server_seed(1), Some(&client));
+    assert_ne!(mixed, game_seed(&server_seed(1), None));
+    assert_ne!(mixed, game_seed(&server_seed(1), Some(&ClientSeed { nonce: 1, ..client.clone() })));
+    assert_ne!(mixed, game_seed(&server_seed(2), Some(&client)));
+}
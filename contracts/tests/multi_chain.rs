@@ -0,0 +1,877 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integration testing for the casino: one Bank chain and several player chains.
+//!
+//! The harness plays the operator: it commits to the server seeds
+//! `server_seed(0..SEEDS)` up front and reveals cards and seeds as the games
+//! need them. Knowing the seeds, it then picks client seeds that deal the
+//! hands each scenario needs, which a real player cannot.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use contracts::fairness::{game_seed, seed_commitment, ClientSeed};
+use contracts::games::blackjack::{
+    calculate_hand_value, next_reveal, perfect_pairs, replay_and_verify, replay_seats, settle_side_bets, BlackjackTable,
+    Reveal, SIDE_BET_MIN_DECKS,
+};
+use contracts::games::shoe::{Shoe, ShoeTable};
+use contracts::rng::RngVersion;
+use contracts::{
+    BaccaratBetType, BaccaratSideBet, BaccaratSideBetType, BaccaratWager, BlackjackRules, CasinoInit, CasinoParams, ContractsAbi, GameAction, GameTimeouts, Operation, RouletteBet,
+    RouletteBetType, ShoeConfig, SideBets,
+};
+use linera_sdk::{
+    linera_base_types::{AccountOwner, ApplicationId, TimeDelta},
+    test::{ActiveChain, MessageAction, QueryOutcome, TestValidator},
+};
+use serde_json::Value;
+
+/// Server seeds committed when the casino opens
+const SEEDS: u64 = 64;
+const STARTING_BALANCE: u64 = 500;
+const HOUSE_BALANCE: u64 = 100_000;
+
+struct Casino {
+    bank: ActiveChain,
+    application_id: ApplicationId<ContractsAbi>,
+    /// Game IDs handed out by the Bank so far
+    games: u64,
+    /// Validator clock in microseconds
+    now: u64,
+    blackjack_rules: BlackjackRules,
+}
+
+impl Casino {
+    /// Publish the module and create the application on a fresh Bank chain
+    async fn new(validator: &TestValidator) -> Self {
+        Self::with_rules(validator, BlackjackRules::default()).await
+    }
+
+    /// A casino dealing blackjack under `blackjack_rules`
+    async fn with_rules(validator: &TestValidator, blackjack_rules: BlackjackRules) -> Self {
+        let mut bank = validator.new_chain().await;
+        let module_id = bank
+            .publish_current_module::<ContractsAbi, CasinoParams, CasinoInit>()
+            .await;
+        let params = CasinoParams {
+            bank_chain_id: bank.id(),
+            timeouts: Default::default(),
+            max_exposure_bps: 5_000,
+            faucet: Default::default(),
+            blackjack_rules,
+            shoe: Default::default(),
+            baccarat_variant: Default::default(),
+        };
+        let init = CasinoInit {
+            starting_balance: STARTING_BALANCE,
+            faucet_pool: 100_000,
+        };
+        let application_id = bank.create_application(module_id, params, init, vec![]).await;
+        let commitments = (0..SEEDS).map(|game_id| seed_commitment(&server_seed(game_id))).collect();
+        bank.add_block(|block| {
+            block.with_operation(application_id, Operation::CommitSeeds { commitments });
+        })
+        .await;
+        Casino { bank, application_id, games: 0, now: 0, blackjack_rules }
+    }
+
+    /// A player chain holding the faucet's starting balance
+    async fn player(&self, validator: &TestValidator) -> ActiveChain {
+        let player = validator.new_chain().await;
+        self.execute(&player, Operation::RequestChips).await;
+        player
+    }
+
+    /// Run an operation on a player chain, then deliver messages and operate
+    /// until every side is idle
+    async fn execute(&self, player: &ActiveChain, operation: Operation) {
+        player
+            .add_block(|block| {
+                block.with_operation(self.application_id, operation);
+            })
+            .await;
+        self.deliver(player).await;
+        self.operate(player).await;
+    }
+
+    /// Act as the operator until no game waits on it
+    async fn operate(&self, player: &ActiveChain) {
+        loop {
+            let operations = self.operator_reveals().await;
+            if operations.is_empty() {
+                break;
+            }
+            self.bank
+                .add_block(|block| {
+                    for operation in operations {
+                        block.with_operation(self.application_id, operation);
+                    }
+                })
+                .await;
+            self.deliver(player).await;
+        }
+    }
+
+    /// The deals, cards and seeds the operator owes the pending games
+    async fn operator_reveals(&self) -> Vec<Operation> {
+        let query = "query { pendingGames { gameId gameType commitment awaitsOperator clientSeed { seed nonce } \
+            seatBets actions revealed shoe { decks shuffles remaining } } }";
+        let response = self.query(&self.bank, query).await;
+        let games = response["pendingGames"].as_array().expect("pendingGames").clone();
+        games
+            .iter()
+            .filter(|game| game["awaitsOperator"] == true)
+            .map(|game| {
+                let game_id = game["gameId"].as_u64().expect("gameId");
+                let server_seed = committed_seed(&game["commitment"]);
+                if game["gameType"] != "BLACKJACK" {
+                    return Operation::RevealSeed { game_id, server_seed };
+                }
+                let client: Option<ClientSeed> = serde_json::from_value(game["clientSeed"].clone()).expect("clientSeed");
+                let shoe: Shoe = serde_json::from_value(game["shoe"].clone()).expect("shoe");
+                let bets: Vec<u64> = serde_json::from_value(game["seatBets"].clone()).expect("seatBets");
+                let actions: Vec<GameAction> = game["actions"].as_array().expect("actions").iter().map(game_action).collect();
+                let revealed = game["revealed"].as_u64().map(|revealed| revealed as usize);
+                let seed = game_seed(&server_seed, client.as_ref());
+                match next_reveal(&shoe, seed, RngVersion::CURRENT, &bets, self.blackjack_rules, &actions, revealed) {
+                    Some(Reveal::Cards(cards)) => Operation::RevealCards { game_id, cards },
+                    Some(Reveal::Seed) => Operation::RevealSeed { game_id, server_seed },
+                    None => panic!("game {game_id} waits on the player"),
+                }
+            })
+            .collect()
+    }
+
+    async fn deliver(&self, player: &ActiveChain) {
+        loop {
+            let bank_moved = self.bank.handle_received_messages().await.is_some();
+            let player_moved = player.handle_received_messages().await.is_some();
+            if !bank_moved && !player_moved {
+                break;
+            }
+        }
+    }
+
+    /// Move the validator clock forward; later blocks run at the new time
+    fn advance(&mut self, validator: &TestValidator, delta: TimeDelta) {
+        validator.clock().add(delta);
+        self.now += delta.as_micros();
+    }
+
+    /// Settle every blackjack round whose player let the decision deadline pass
+    async fn expire_turns(&self, player: &ActiveChain) {
+        let response = self.query(&self.bank, "query { pendingGames { gameId commitment awaitsOperator waitingSince } }").await;
+        let timeouts = GameTimeouts::default();
+        let expired: Vec<Operation> = response["pendingGames"]
+            .as_array()
+            .expect("pendingGames")
+            .iter()
+            .filter(|game| {
+                game["awaitsOperator"] == false
+                    && timeouts.decision_expired(game["waitingSince"].as_u64().expect("waitingSince"), self.now)
+            })
+            .map(|game| Operation::RevealSeed {
+                game_id: game["gameId"].as_u64().expect("gameId"),
+                server_seed: committed_seed(&game["commitment"]),
+            })
+            .collect();
+        self.bank
+            .add_block(|block| {
+                for operation in expired {
+                    block.with_operation(self.application_id, operation);
+                }
+            })
+            .await;
+        self.deliver(player).await;
+    }
+
+    async fn query(&self, chain: &ActiveChain, query: &str) -> Value {
+        let QueryOutcome { response, .. } = chain.graphql_query(self.application_id, query).await;
+        response
+    }
+
+    async fn player_balance(&self, player: &ActiveChain) -> u64 {
+        self.query(player, "query { playerBalance }").await["playerBalance"]
+            .as_u64()
+            .expect("playerBalance")
+    }
+
+    async fn house_balance(&self) -> u64 {
+        self.query(&self.bank, "query { houseBalance }").await["houseBalance"]
+            .as_u64()
+            .expect("houseBalance")
+    }
+
+    async fn last_record(&self, player: &ActiveChain) -> Value {
+        let response = self
+            .query(player, "query { gameHistory { gameId gameType result payout bet hands { bet fromSplit result payout } } }")
+            .await;
+        response["gameHistory"]
+            .as_array()
+            .and_then(|history| history.last().cloned())
+            .expect("empty history")
+    }
+
+    async fn verify(&self, player: &ActiveChain, game_id: u64) -> bool {
+        let query = format!("query {{ verifyGame(gameId: {game_id}) {{ passed mismatches }} }}");
+        let report = self.query(player, &query).await;
+        assert_eq!(report["verifyGame"]["mismatches"], Value::Array(vec![]), "game {game_id}");
+        report["verifyGame"]["passed"].as_bool().expect("passed")
+    }
+
+    /// Blackjack shoe tables on the Bank
+    async fn blackjack_tables(&self) -> Vec<ShoeTable> {
+        let response = self.query(&self.bank, "query { shoes(gameType: BLACKJACK) { round shoe { decks shuffles remaining } } }").await;
+        serde_json::from_value(response["shoes"].clone()).expect("shoes")
+    }
+
+    /// Shoe the Bank will deal the next single-seat blackjack round from
+    async fn blackjack_shoe(&self) -> Shoe {
+        self.blackjack_shoe_for(1).await
+    }
+
+    /// Shoe the Bank will deal the next blackjack round at `seats` seats from: the first free table's
+    async fn blackjack_shoe_for(&self, seats: usize) -> Shoe {
+        let tables = self.blackjack_tables().await;
+        let mut shoe = match tables.into_iter().find(|table| table.round.is_none()) {
+            Some(table) => table.shoe,
+            None => Shoe::new(self.blackjack_rules.decks),
+        };
+        if shoe.past_cut_card(ShoeConfig::default().penetration_percent, seats) {
+            shoe.reshuffle();
+        }
+        shoe
+    }
+
+    /// Reserve `player` a seed commitment and return the ID the Bank will give
+    /// its next game. Every scenario plays each reservation, so game N is also
+    /// bound to the Nth commitment and dealt from `server_seed(N)`.
+    async fn next_game(&mut self, player: &ActiveChain) -> u64 {
+        self.execute(player, Operation::RequestSeedCommitment).await;
+        let game_id = self.games;
+        self.games += 1;
+        game_id
+    }
+
+    /// First client seed whose game seed satisfies `wanted`
+    fn client_seed_for(&self, game_id: u64, wanted: impl Fn(u64) -> bool) -> ClientSeed {
+        let server_seed = server_seed(game_id);
+        (0..)
+            .map(|nonce| ClientSeed { seed: "test".to_string(), nonce })
+            .find(|client| wanted(game_seed(&server_seed, Some(client))))
+            .expect("no client seed deals the wanted hand")
+    }
+
+    /// What the Bank will pay for a blackjack game played with `actions`
+    fn expected_payout(&self, game_id: u64, shoe: &Shoe, bet: u64, client: &ClientSeed, actions: &[GameAction]) -> u64 {
+        let seed = game_seed(&server_seed(game_id), Some(client));
+        replay_and_verify(shoe, seed, RngVersion::CURRENT, bet, self.blackjack_rules, actions)
+            .settle()
+            .1
+    }
+}
+
+/// The operator's `index`-th server seed
+fn server_seed(index: u64) -> String {
+    format!("{index:064x}")
+}
+
+/// Server seed the operator published `commitment` for
+fn committed_seed(commitment: &Value) -> String {
+    (0..SEEDS)
+        .map(server_seed)
+        .find(|seed| commitment.as_str() == Some(seed_commitment(seed).as_str()))
+        .expect("the operator never published this commitment")
+}
+
+fn play_blackjack(bet: u64, client: &ClientSeed) -> Operation {
+    play_blackjack_with_side_bets(bet, None, client)
+}
+
+fn play_blackjack_with_side_bets(bet: u64, side_bets: Option<SideBets>, client: &ClientSeed) -> Operation {
+    Operation::PlayBlackjack {
+        bet,
+        client_seed: Some(client.seed.clone()),
+        nonce: Some(client.nonce),
+        side_bets,
+    }
+}
+
+/// Value of a card for split purposes
+fn split_value(value: &str) -> u8 {
+    match value {
+        "ace" => 11,
+        "king" | "queen" | "jack" => 10,
+        v => v.parse().unwrap_or(0),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn faucet_grants_chips_then_enforces_the_cooldown() {
+    let validator = TestValidator::new().await;
+    let casino = Casino::new(&validator).await;
+    let player = casino.player(&validator).await;
+
+    assert_eq!(casino.player_balance(&player).await, STARTING_BALANCE);
+
+    // A second claim inside the cooldown is rejected by the Bank and bounces
+    let (certificate, _) = player
+        .add_block(|block| {
+            block.with_operation(casino.application_id, Operation::RequestChips);
+        })
+        .await;
+    assert!(casino
+        .bank
+        .try_add_block(|block| {
+            block.with_messages_from(&certificate);
+        })
+        .await
+        .is_err());
+    casino
+        .bank
+        .add_block(|block| {
+            block.with_messages_from_by_action(&certificate, MessageAction::Reject);
+        })
+        .await;
+    casino.deliver(&player).await;
+
+    assert_eq!(casino.player_balance(&player).await, STARTING_BALANCE);
+    let owner = AccountOwner::from(player.public_key());
+    let query = format!("query {{ faucetPool faucetNextClaimAt(owner: \"{owner}\") }}");
+    let response = casino.query(&casino.bank, &query).await;
+    assert_eq!(response["faucetPool"].as_u64(), Some(100_000 - STARTING_BALANCE));
+    assert!(response["faucetNextClaimAt"].as_u64() > Some(0));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn blackjack_rounds_settle_through_the_bank() {
+    let validator = TestValidator::new().await;
+    let mut casino = Casino::new(&validator).await;
+    let player = casino.player(&validator).await;
+    let bet = 10;
+    let mut balance = STARTING_BALANCE;
+    let mut house = HOUSE_BALANCE;
+
+    let rules = casino.query(&player, "query { blackjackRules { decks blackjackPayout maxSplitHands } }").await;
+    assert_eq!(rules["blackjackRules"]["decks"].as_u64(), Some(1));
+    assert_eq!(rules["blackjackRules"]["blackjackPayout"], "THREE_TO_TWO");
+
+    // Hit once without busting, then stand
+    let shoe = casino.blackjack_shoe().await;
+    let game_id = casino.next_game(&player).await;
+    let client = casino.client_seed_for(game_id, |seed| {
+        !replay(&shoe, seed, bet, &[GameAction::Hit]).is_finished()
+    });
+    let actions = [GameAction::Hit, GameAction::Stand];
+    let payout = casino.expected_payout(game_id, &shoe, bet, &client, &actions);
+    casino.execute(&player, play_blackjack(bet, &client)).await;
+    let game = casino.query(&player, "query { currentGame { gameId phase dealerHand { value } } }").await;
+    assert_eq!(game["currentGame"]["gameId"].as_u64(), Some(game_id));
+    assert_eq!(game["currentGame"]["phase"], "PLAYER_TURN");
+    assert_eq!(game["currentGame"]["dealerHand"].as_array().map(Vec::len), Some(1), "hole card stays hidden");
+    for action in actions {
+        casino.execute(&player, action_operation(action)).await;
+    }
+    balance = balance - bet + payout;
+    house = house + bet - payout;
+    assert_eq!(casino.player_balance(&player).await, balance);
+    assert_eq!(casino.house_balance().await, house);
+    assert_eq!(casino.last_record(&player).await["payout"].as_u64(), Some(payout));
+    assert!(casino.verify(&player, game_id).await);
+
+    // Naturals on either side settle at the deal, without any player action
+    let naturals: [fn(&BlackjackTable) -> bool; 2] = [
+        |table| calculate_hand_value(&table.player_hands[0]) == 21 && !table.insurance_offered(),
+        |table| calculate_hand_value(&table.dealer_hand) == 21 && !table.insurance_offered(),
+    ];
+    for natural in naturals {
+        let shoe = casino.blackjack_shoe().await;
+        let game_id = casino.next_game(&player).await;
+        let client = casino.client_seed_for(game_id, |seed| natural(&deal(&shoe, seed, bet)));
+        let payout = casino.expected_payout(game_id, &shoe, bet, &client, &[]);
+        casino.execute(&player, play_blackjack(bet, &client)).await;
+        let game = casino.query(&player, "query { currentGame { gameId } }").await;
+        assert!(game["currentGame"].is_null(), "the round settled on its own");
+        balance = balance - bet + payout;
+        house = house + bet - payout;
+        assert_eq!(casino.player_balance(&player).await, balance);
+        assert_eq!(casino.house_balance().await, house);
+        assert_eq!(casino.last_record(&player).await["gameId"].as_u64(), Some(game_id));
+        assert!(casino.verify(&player, game_id).await);
+    }
+
+    // Double down
+    let shoe = casino.blackjack_shoe().await;
+    let game_id = casino.next_game(&player).await;
+    let client = casino.client_seed_for(game_id, |seed| !deal(&shoe, seed, bet).has_natural());
+    let payout = casino.expected_payout(game_id, &shoe, bet, &client, &[GameAction::DoubleDown]);
+    casino.execute(&player, play_blackjack(bet, &client)).await;
+    casino.execute(&player, Operation::DoubleDown).await;
+    balance = balance - 2 * bet + payout;
+    house = house + 2 * bet - payout;
+    assert_eq!(casino.player_balance(&player).await, balance);
+    assert_eq!(casino.house_balance().await, house);
+    assert!(casino.verify(&player, game_id).await);
+
+    // Split a pair, then stand on both hands
+    let shoe = casino.blackjack_shoe().await;
+    let game_id = casino.next_game(&player).await;
+    let client = casino.client_seed_for(game_id, |seed| {
+        let table = deal(&shoe, seed, bet);
+        let hand = &table.player_hands[0];
+        !table.has_natural()
+            && split_value(&hand[0].value) == split_value(&hand[1].value)
+            && hand[0].value != "ace"
+    });
+    let actions = [GameAction::Split, GameAction::Stand, GameAction::Stand];
+    let payout = casino.expected_payout(game_id, &shoe, bet, &client, &actions);
+    casino.execute(&player, play_blackjack(bet, &client)).await;
+    for action in actions {
+        casino.execute(&player, action_operation(action)).await;
+    }
+    balance = balance - 2 * bet + payout;
+    house = house + 2 * bet - payout;
+    assert_eq!(casino.player_balance(&player).await, balance);
+    assert_eq!(casino.house_balance().await, house);
+    let record = casino.last_record(&player).await;
+    assert_eq!(record["gameId"].as_u64(), Some(game_id));
+    let hands = record["hands"].as_array().expect("split hands are recorded");
+    assert_eq!(hands.len(), 2);
+    assert!(hands.iter().all(|hand| hand["fromSplit"] == true && hand["bet"].as_u64() == Some(bet)));
+    let hand_payouts: u64 = hands.iter().map(|hand| hand["payout"].as_u64().unwrap()).sum();
+    assert_eq!(hand_payouts, payout);
+    assert!(casino.verify(&player, game_id).await);
+
+    // Insure against a dealer ace that hides a natural: the insurance pays 2:1
+    let shoe = casino.blackjack_shoe().await;
+    let game_id = casino.next_game(&player).await;
+    let client = casino.client_seed_for(game_id, |seed| {
+        let table = deal(&shoe, seed, bet);
+        table.insurance_offered() && table.has_natural() && calculate_hand_value(&table.player_hands[0]) < 21
+    });
+    casino.execute(&player, play_blackjack(bet, &client)).await;
+    let game = casino.query(&player, "query { currentGame { availableActions } }").await;
+    assert!(game["currentGame"]["availableActions"]
+        .as_array()
+        .expect("availableActions")
+        .contains(&Value::from("INSURANCE")));
+    casino.execute(&player, Operation::Insurance).await;
+    // The main bet is lost and the insurance returns three times its stake
+    let insurance = bet / 2;
+    balance = balance - bet - insurance + 3 * insurance;
+    house = house + bet + insurance - 3 * insurance;
+    assert_eq!(casino.player_balance(&player).await, balance);
+    assert_eq!(casino.house_balance().await, house);
+    assert_eq!(casino.last_record(&player).await["payout"].as_u64(), Some(3 * insurance));
+    assert!(casino.verify(&player, game_id).await);
+
+    // Surrender the opening hand for half the bet
+    let shoe = casino.blackjack_shoe().await;
+    let game_id = casino.next_game(&player).await;
+    let client = casino.client_seed_for(game_id, |seed| {
+        let table = deal(&shoe, seed, bet);
+        !table.has_natural() && !table.insurance_offered()
+    });
+    casino.execute(&player, play_blackjack(bet, &client)).await;
+    casino.execute(&player, Operation::Surrender).await;
+    balance -= bet / 2;
+    house += bet / 2;
+    assert_eq!(casino.player_balance(&player).await, balance);
+    assert_eq!(casino.house_balance().await, house);
+    assert_eq!(casino.last_record(&player).await["result"], "SURRENDER");
+    assert!(casino.verify(&player, game_id).await);
+
+    // Two seats against one dealer hand: stand on each seat in turn
+    let shoe = casino.blackjack_shoe_for(2).await;
+    let game_id = casino.next_game(&player).await;
+    let bets = [10, 30];
+    let seats = |seed| BlackjackTable::deal_seats(&shoe, seed, RngVersion::CURRENT, &bets, BlackjackRules::default());
+    let client = casino.client_seed_for(game_id, |seed| {
+        let table = seats(seed);
+        !table.is_finished() && table.hands.iter().all(|hand| !hand.finished)
+    });
+    let actions = [GameAction::Stand, GameAction::Stand];
+    let seed = game_seed(&server_seed(game_id), Some(&client));
+    let payout = replay_seats(&shoe, seed, RngVersion::CURRENT, &bets, BlackjackRules::default(), &actions).settle().1;
+    casino.execute(&player, Operation::PlayBlackjackMulti {
+        bets: bets.to_vec(),
+        client_seed: Some(client.seed.clone()),
+        nonce: Some(client.nonce),
+    }).await;
+    let game = casino.query(&player, "query { currentGame { activeHandIndex hands { seat bet } } }").await;
+    assert_eq!(game["currentGame"]["hands"].as_array().map(Vec::len), Some(2));
+    assert_eq!(game["currentGame"]["hands"][1]["bet"].as_u64(), Some(30));
+    for action in actions {
+        casino.execute(&player, action_operation(action)).await;
+    }
+    balance = balance - 40 + payout;
+    house = house + 40 - payout;
+    assert_eq!(casino.player_balance(&player).await, balance);
+    assert_eq!(casino.house_balance().await, house);
+    let record = casino.query(&player, "query { gameHistory { bet payout seats { seat bet payout } } }").await;
+    let record = record["gameHistory"].as_array().and_then(|history| history.last().cloned()).expect("record");
+    assert_eq!(record["bet"].as_u64(), Some(40));
+    let seat_payouts: Vec<u64> = record["seats"].as_array().expect("seats").iter()
+        .map(|seat| seat["payout"].as_u64().unwrap())
+        .collect();
+    assert_eq!(seat_payouts.len(), 2);
+    assert_eq!(seat_payouts.iter().sum::<u64>(), payout);
+    assert!(casino.verify(&player, game_id).await);
+
+    let response = casino.query(&casino.bank, "query { outstandingLiability }").await;
+    assert_eq!(response["outstandingLiability"].as_u64(), Some(0));
+
+    // The single-deck shoe carried over between rounds and passed its cut card
+    let shoe = casino.blackjack_shoe().await;
+    assert!(shoe.shuffles > 0);
+    assert_eq!(shoe.cards_left() + shoe.dealt(), 52);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn side_bets_are_only_taken_from_a_six_deck_shoe() {
+    let validator = TestValidator::new().await;
+
+    // The default single-deck table refuses them
+    let mut casino = Casino::new(&validator).await;
+    let player = casino.player(&validator).await;
+    casino.next_game(&player).await;
+    let side_bets = SideBets { perfect_pairs: 10, twenty_one_plus_three: 10 };
+    let client = ClientSeed { seed: "test".to_string(), nonce: 0 };
+    assert!(player
+        .try_add_block(|block| {
+            block.with_operation(casino.application_id, play_blackjack_with_side_bets(10, Some(side_bets), &client));
+        })
+        .await
+        .is_err());
+
+    // Side bets on a dealt pair are paid on top of the main bet
+    let rules = BlackjackRules { decks: SIDE_BET_MIN_DECKS, ..Default::default() };
+    let mut casino = Casino::with_rules(&validator, rules).await;
+    let player = casino.player(&validator).await;
+    let bet = 10;
+    let shoe = casino.blackjack_shoe().await;
+    let game_id = casino.next_game(&player).await;
+    let deal = |seed| BlackjackTable::deal(&shoe, seed, RngVersion::CURRENT, bet, rules);
+    let client = casino.client_seed_for(game_id, |seed| {
+        let table = deal(seed);
+        let hand = &table.player_hands[0];
+        !table.has_natural() && !table.insurance_offered() && perfect_pairs(&hand[0], &hand[1]).is_some()
+    });
+    let table = deal(game_seed(&server_seed(game_id), Some(&client)));
+    let side_payout: u64 = settle_side_bets(&side_bets, &table.player_hands[0], &table.dealer_hand[0])
+        .iter()
+        .map(|side_bet| side_bet.payout)
+        .sum();
+    assert!(side_payout > 0);
+    let payout = casino.expected_payout(game_id, &shoe, bet, &client, &[GameAction::Stand]);
+    casino.execute(&player, play_blackjack_with_side_bets(bet, Some(side_bets), &client)).await;
+    casino.execute(&player, Operation::Stand).await;
+    let staked = bet + side_bets.total();
+    assert_eq!(casino.player_balance(&player).await, STARTING_BALANCE - staked + payout + side_payout);
+    assert_eq!(casino.house_balance().await, HOUSE_BALANCE + staked - payout - side_payout);
+    let record = casino.query(&player, "query { gameHistory { payout sideBets { kind outcome payout } } }").await;
+    let record = record["gameHistory"].as_array().and_then(|history| history.last().cloned()).expect("record");
+    assert_eq!(record["payout"].as_u64(), Some(payout), "side bets are reported apart");
+    assert_eq!(record["sideBets"].as_array().map(Vec::len), Some(2));
+    assert!(casino.verify(&player, game_id).await);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn rejecting_revealed_cards_does_not_refund_the_stake() {
+    let validator = TestValidator::new().await;
+    let mut casino = Casino::new(&validator).await;
+    let player = casino.player(&validator).await;
+    let bet = 10;
+
+    // A hit the player would lose on after standing
+    let shoe = casino.blackjack_shoe().await;
+    let game_id = casino.next_game(&player).await;
+    let client = casino.client_seed_for(game_id, |seed| {
+        !replay(&shoe, seed, bet, &[GameAction::Hit]).is_finished()
+            && replay(&shoe, seed, bet, &[GameAction::Hit, GameAction::Stand]).settle().1 == 0
+    });
+    casino.execute(&player, play_blackjack(bet, &client)).await;
+
+    // The player sees the hit card coming back and refuses it
+    let (hit, _) = player
+        .add_block(|block| {
+            block.with_operation(casino.application_id, Operation::Hit);
+        })
+        .await;
+    casino
+        .bank
+        .add_block(|block| {
+            block.with_messages_from(&hit);
+        })
+        .await;
+    let reveals = casino.operator_reveals().await;
+    let (cards, _) = casino
+        .bank
+        .add_block(|block| {
+            for operation in reveals {
+                block.with_operation(casino.application_id, operation);
+            }
+        })
+        .await;
+    player
+        .add_block(|block| {
+            block.with_messages_from_by_action(&cards, MessageAction::Reject);
+        })
+        .await;
+    casino.deliver(&player).await;
+    assert_eq!(casino.player_balance(&player).await, STARTING_BALANCE - bet);
+
+    // Past the deadline the Bank stands on the hit hand and keeps the stake
+    casino.advance(&validator, TimeDelta::from_micros(GameTimeouts::default().blackjack_micros));
+    casino.expire_turns(&player).await;
+    assert_eq!(casino.player_balance(&player).await, STARTING_BALANCE - bet);
+    assert_eq!(casino.house_balance().await, HOUSE_BALANCE + bet);
+    let record = casino.last_record(&player).await;
+    assert_eq!(record["gameId"].as_u64(), Some(game_id));
+    assert_eq!(record["payout"].as_u64(), Some(0));
+    assert!(casino.verify(&player, game_id).await);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn late_settlement_of_an_abandoned_game_leaves_the_next_one_alone() {
+    let validator = TestValidator::new().await;
+    let mut casino = Casino::new(&validator).await;
+    let player = casino.player(&validator).await;
+    let bet = 10;
+    let open = |shoe: &Shoe, seed| {
+        let table = deal(shoe, seed, bet);
+        !table.has_natural() && !table.insurance_offered()
+    };
+
+    // Leave a round open past its deadline and abandon it
+    let shoe = casino.blackjack_shoe().await;
+    let abandoned_id = casino.next_game(&player).await;
+    let client = casino.client_seed_for(abandoned_id, |seed| open(&shoe, seed));
+    let late_payout = casino.expected_payout(abandoned_id, &shoe, bet, &client, &[GameAction::Stand]);
+    casino.execute(&player, play_blackjack(bet, &client)).await;
+    casino.advance(&validator, TimeDelta::from_micros(GameTimeouts::default().blackjack_micros));
+    casino.execute(&player, Operation::AbandonGame).await;
+    assert_eq!(casino.last_record(&player).await["result"], "ABANDONED");
+
+    // Start the next round before the Bank expires the abandoned one: it gets a
+    // shoe of its own rather than the cards the open round may still draw
+    let shoe = casino.blackjack_shoe().await;
+    let game_id = casino.next_game(&player).await;
+    let client = casino.client_seed_for(game_id, |seed| open(&shoe, seed));
+    let payout = casino.expected_payout(game_id, &shoe, bet, &client, &[GameAction::Stand]);
+    casino.execute(&player, play_blackjack(bet, &client)).await;
+    let rounds: Vec<Option<u64>> = casino.blackjack_tables().await.iter().map(|table| table.round).collect();
+    assert_eq!(rounds, [Some(abandoned_id), Some(game_id)]);
+
+    // The late settlement is credited and recorded without touching the new round
+    casino.expire_turns(&player).await;
+    let game = casino.query(&player, "query { currentGame { gameId phase } }").await;
+    assert_eq!(game["currentGame"]["gameId"].as_u64(), Some(game_id));
+    assert_eq!(game["currentGame"]["phase"], "PLAYER_TURN");
+    assert_eq!(casino.player_balance(&player).await, STARTING_BALANCE - 2 * bet + late_payout);
+    let record = casino.last_record(&player).await;
+    assert_eq!(record["gameId"].as_u64(), Some(abandoned_id));
+    assert_eq!(record["payout"].as_u64(), Some(late_payout));
+    assert!(casino.verify(&player, abandoned_id).await);
+
+    casino.execute(&player, Operation::Stand).await;
+    assert_eq!(casino.player_balance(&player).await, STARTING_BALANCE - 2 * bet + late_payout + payout);
+    assert_eq!(casino.house_balance().await, HOUSE_BALANCE + 2 * bet - late_payout - payout);
+    assert!(casino.verify(&player, game_id).await);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn games_the_operator_leaves_unrevealed_are_forfeited() {
+    let validator = TestValidator::new().await;
+    let mut casino = Casino::new(&validator).await;
+    let player = casino.player(&validator).await;
+
+    // A spin the operator never settles
+    let game_id = casino.next_game(&player).await;
+    let bets = vec![RouletteBet { bet_type: RouletteBetType::Red, number: None, numbers: None, amount: 10 }];
+    player
+        .add_block(|block| {
+            block.with_operation(casino.application_id, Operation::PlayRoulette { bets, client_seed: None, nonce: None });
+        })
+        .await;
+    casino.deliver(&player).await;
+    let response = casino.query(&casino.bank, "query { pendingGames { staked maxLiability } }").await;
+    let staked = response["pendingGames"][0]["staked"].as_u64().expect("staked");
+    let max_liability = response["pendingGames"][0]["maxLiability"].as_u64().expect("maxLiability");
+    assert_eq!(staked, 10);
+
+    // Only the committed seed settles it
+    assert!(casino
+        .bank
+        .try_add_block(|block| {
+            block.with_operation(casino.application_id, Operation::RevealSeed { game_id, server_seed: server_seed(game_id + 1) });
+        })
+        .await
+        .is_err());
+
+    // Past the reveal deadline the player is paid the most the spin could have won
+    casino.advance(&validator, TimeDelta::from_micros(GameTimeouts::default().reveal_micros));
+    casino.execute(&player, Operation::ClaimUnrevealed).await;
+    assert_eq!(casino.player_balance(&player).await, STARTING_BALANCE + max_liability);
+    assert_eq!(casino.house_balance().await, HOUSE_BALANCE - max_liability);
+    let record = casino.last_record(&player).await;
+    assert_eq!(record["gameId"].as_u64(), Some(game_id));
+    assert_eq!(record["result"], "FORFEITED");
+    assert_eq!(record["payout"].as_u64(), Some(staked + max_liability));
+    let response = casino.query(&casino.bank, "query { pendingGames { gameId } outstandingLiability }").await;
+    assert_eq!(response["pendingGames"], Value::Array(vec![]));
+    assert_eq!(response["outstandingLiability"].as_u64(), Some(0));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn roulette_and_baccarat_settle_for_several_players() {
+    let validator = TestValidator::new().await;
+    let mut casino = Casino::new(&validator).await;
+    let alice = casino.player(&validator).await;
+    let bob = casino.player(&validator).await;
+
+    // Every game needs a seed commitment reserved before its client seed is picked
+    let bets = vec![
+        RouletteBet { bet_type: RouletteBetType::Red, number: None, numbers: None, amount: 5 },
+        RouletteBet { bet_type: RouletteBetType::Number, number: Some(7), numbers: None, amount: 1 },
+    ];
+    let play_roulette = Operation::PlayRoulette { bets: bets.clone(), client_seed: None, nonce: None };
+    assert!(alice
+        .try_add_block(|block| {
+            block.with_operation(casino.application_id, play_roulette);
+        })
+        .await
+        .is_err());
+    let roulette_id = casino.next_game(&alice).await;
+    let commitment = seed_commitment(&server_seed(roulette_id));
+    assert_eq!(casino.query(&alice, "query { seedCommitment }").await["seedCommitment"], commitment.as_str());
+    // Asking again sends the same reservation back
+    casino.execute(&alice, Operation::RequestSeedCommitment).await;
+    assert_eq!(casino.query(&alice, "query { seedCommitment }").await["seedCommitment"], commitment.as_str());
+    let response = casino.query(&casino.bank, "query { seedCommitmentsLeft }").await;
+    assert_eq!(response["seedCommitmentsLeft"].as_u64(), Some(SEEDS - 1));
+
+    // Roulette: the Bank spins once the operator reveals the seed
+    casino
+        .execute(&alice, Operation::PlayRoulette { bets, client_seed: None, nonce: None })
+        .await;
+    let record = casino.last_record(&alice).await;
+    assert_eq!(record["gameId"].as_u64(), Some(roulette_id));
+    assert_eq!(record["gameType"], "ROULETTE");
+    let roulette_payout = record["payout"].as_u64().expect("payout");
+    assert_eq!(casino.player_balance(&alice).await, STARTING_BALANCE - 6 + roulette_payout);
+    assert!(casino.verify(&alice, roulette_id).await);
+
+    // Baccarat settles as soon as the Bank receives the request, side bets included
+    let baccarat_id = casino.next_game(&bob).await;
+    casino
+        .execute(
+            &bob,
+            Operation::PlayBaccarat {
+                amount: 10,
+                bet_type: BaccaratBetType::Banker,
+                client_seed: Some("bob".to_string()),
+                nonce: None,
+                side_bets: Some(vec![
+                    BaccaratSideBet { bet_type: BaccaratSideBetType::PlayerPair, amount: 2 },
+                    BaccaratSideBet { bet_type: BaccaratSideBetType::DragonBanker, amount: 3 },
+                ]),
+            },
+        )
+        .await;
+    let record = casino.last_record(&bob).await;
+    assert_eq!(record["gameId"].as_u64(), Some(baccarat_id));
+    assert_eq!(record["gameType"], "BACCARAT");
+    let baccarat_payout = record["payout"].as_u64().expect("payout");
+    let response = casino.query(&bob, "query { gameHistory { baccaratWinner baccaratSideBets { betType amount payout } } }").await;
+    let banker_won = response["gameHistory"][0]["baccaratWinner"] == "BANKER";
+    let side_bets = response["gameHistory"][0]["baccaratSideBets"].as_array().expect("side bets").clone();
+    assert_eq!(side_bets.len(), 2);
+    let side_payout: u64 = side_bets.iter().map(|side_bet| side_bet["payout"].as_u64().unwrap()).sum();
+    assert_eq!(casino.player_balance(&bob).await, STARTING_BALANCE - 15 + baccarat_payout + side_payout);
+    assert!(casino.verify(&bob, baccarat_id).await);
+
+    // Several main bets share one coup and one record
+    let multi_id = casino.next_game(&bob).await;
+    casino
+        .execute(
+            &bob,
+            Operation::PlayBaccaratMulti {
+                wagers: vec![
+                    BaccaratWager { bet_type: BaccaratBetType::Player, amount: 10 },
+                    BaccaratWager { bet_type: BaccaratBetType::Tie, amount: 2 },
+                ],
+                client_seed: None,
+                nonce: None,
+                side_bets: None,
+            },
+        )
+        .await;
+    let response = casino.query(&bob, "query { gameHistory { gameId bet payout baccaratWinner baccaratWagers { betType amount payout } } }").await;
+    let record = &response["gameHistory"][1];
+    assert_eq!(record["gameId"].as_u64(), Some(multi_id));
+    assert_eq!(record["bet"].as_u64(), Some(12));
+    let wagers = record["baccaratWagers"].as_array().expect("wagers");
+    assert_eq!(wagers.len(), 2);
+    let multi_payout: u64 = wagers.iter().map(|wager| wager["payout"].as_u64().unwrap()).sum();
+    assert_eq!(record["payout"].as_u64(), Some(multi_payout));
+    let expected = match record["baccaratWinner"].as_str() {
+        Some("PLAYER") => 20,
+        Some("TIE") => 10 + 18,
+        _ => 0,
+    };
+    assert_eq!(multi_payout, expected);
+    assert_eq!(
+        casino.player_balance(&bob).await,
+        STARTING_BALANCE - 15 - 12 + baccarat_payout + side_payout + multi_payout
+    );
+    assert!(casino.verify(&bob, multi_id).await);
+
+    // A Classic Banker win of 10 pays 19.50: the half chip stays owed to bob
+    let owner = AccountOwner::from(bob.public_key());
+    let query = format!("query {{ baccaratVariant baccaratCommissionCredit(owner: \"{owner}\") }}");
+    let response = casino.query(&casino.bank, &query).await;
+    assert_eq!(response["baccaratVariant"], "CLASSIC");
+    assert_eq!(response["baccaratCommissionCredit"].as_u64(), Some(if banker_won { 50 } else { 0 }));
+
+    // House P&L is the mirror of both players' results
+    assert_eq!(
+        casino.house_balance().await,
+        HOUSE_BALANCE + 6 + 15 + 12 - roulette_payout - baccarat_payout - side_payout - multi_payout
+    );
+}
+
+fn action_operation(action: GameAction) -> Operation {
+    match action {
+        GameAction::Hit => Operation::Hit,
+        GameAction::Stand => Operation::Stand,
+        GameAction::DoubleDown => Operation::DoubleDown,
+        GameAction::Split => Operation::Split,
+        GameAction::Insurance => Operation::Insurance,
+        GameAction::EvenMoney => Operation::EvenMoney,
+        GameAction::Surrender => Operation::Surrender,
+    }
+}
+
+/// Action from its GraphQL name
+fn game_action(name: &Value) -> GameAction {
+    match name.as_str().expect("action name") {
+        "HIT" => GameAction::Hit,
+        "STAND" => GameAction::Stand,
+        "DOUBLE_DOWN" => GameAction::DoubleDown,
+        "SPLIT" => GameAction::Split,
+        "INSURANCE" => GameAction::Insurance,
+        "EVEN_MONEY" => GameAction::EvenMoney,
+        "SURRENDER" => GameAction::Surrender,
+        other => panic!("unknown action {other}"),
+    }
+}
+
+fn deal(shoe: &Shoe, seed: u64, bet: u64) -> BlackjackTable {
+    BlackjackTable::deal(shoe, seed, RngVersion::CURRENT, bet, BlackjackRules::default())
+}
+
+fn replay(shoe: &Shoe, seed: u64, bet: u64, actions: &[GameAction]) -> BlackjackTable {
+    replay_and_verify(shoe, seed, RngVersion::CURRENT, bet, BlackjackRules::default(), actions)
+}
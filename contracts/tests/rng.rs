@@ -0,0 +1,59 @@
+//! Generator selection and bounded sampling.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use contracts::rng::{shuffle, GameRng, RngVersion, XorShiftRng};
+
+#[test]
+fn xorshift_keeps_the_original_modulo_sampling() {
+    // Hand-rolled copy of the pre-versioning SimpleRng spin
+    let seed = 12345u64;
+    let mut state = seed;
+    state ^= state << 7;
+    state ^= state >> 9;
+    state ^= state << 8;
+
+    let mut rng = RngVersion::XorShift.rng(seed);
+    assert_eq!(rng.below(37), state % 37);
+    assert_eq!(XorShiftRng::new(0).next_u64(), XorShiftRng::new(0x9e3779b185ebca87).next_u64());
+}
+
+#[test]
+fn xorshift_shuffles_like_the_original_wasm_build() {
+    // Baseline `shuffle` on wasm32, where `(rng.next() as usize) % (i + 1)`
+    // only saw the low 32 bits of each draw
+    let expected: Vec<u32> = vec![
+        26, 13, 40, 8, 39, 35, 33, 44, 47, 36, 21, 48, 31, 7, 24, 10, 12, 4, 50, 46, 42, 14, 16, 9, 41, 23,
+        27, 15, 22, 43, 37, 51, 38, 0, 29, 19, 6, 17, 25, 32, 5, 45, 30, 18, 11, 20, 1, 34, 28, 49, 2, 3,
+    ];
+    let mut deck: Vec<u32> = (0..52).collect();
+    shuffle(&mut deck, RngVersion::XorShift.rng(12345).as_mut());
+    assert_eq!(deck, expected);
+}
+
+#[test]
+fn same_seed_same_version_replays() {
+    for version in [RngVersion::XorShift, RngVersion::ChaCha20V1] {
+        let mut a: Vec<u32> = (0..52).collect();
+        let mut b = a.clone();
+        shuffle(&mut a, version.rng(99).as_mut());
+        shuffle(&mut b, version.rng(99).as_mut());
+        assert_eq!(a, b);
+    }
+
+    let mut legacy: Vec<u32> = (0..52).collect();
+    let mut current = legacy.clone();
+    shuffle(&mut legacy, RngVersion::XorShift.rng(99).as_mut());
+    shuffle(&mut current, RngVersion::ChaCha20V1.rng(99).as_mut());
+    assert_ne!(legacy, current);
+}
+
+#[test]
+fn chacha_spins_cover_the_wheel() {
+    let mut rng = RngVersion::ChaCha20V1.rng(7);
+    let mut seen = [0u32; 37];
+    for _ in 0..37_000 {
+        seen[rng.below(37) as usize] += 1;
+    }
+    assert!(seen.iter().all(|&count| (700..1300).contains(&count)), "{:?}", seen);
+}
@@ -0,0 +1,91 @@
+//! Table-geometry checks for roulette bets.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use contracts::games::roulette::{
+    calculate_roulette_payout, roulette_max_liability, spin_roulette, validate_bet, validate_bets,
+    RouletteBetError, MAX_NUMBER,
+};
+use contracts::rng::RngVersion;
+use contracts::{RouletteBet, RouletteBetType};
+
+fn inside(bet_type: RouletteBetType, numbers: &[u8]) -> RouletteBet {
+    RouletteBet {
+        bet_type,
+        number: None,
+        numbers: Some(numbers.to_vec()),
+        amount: 1,
+    }
+}
+
+#[test]
+fn accepts_shapes_on_the_layout() {
+    for numbers in [[0, 1], [0, 3], [1, 2], [2, 3], [1, 4], [33, 36]] {
+        assert_eq!(validate_bet(&inside(RouletteBetType::Split, &numbers)), Ok(()));
+    }
+    for numbers in [[0, 1, 2], [0, 2, 3], [1, 2, 3], [34, 35, 36]] {
+        assert_eq!(validate_bet(&inside(RouletteBetType::Street, &numbers)), Ok(()));
+    }
+    for numbers in [[1, 2, 4, 5], [2, 3, 5, 6], [32, 33, 35, 36]] {
+        assert_eq!(validate_bet(&inside(RouletteBetType::Corner, &numbers)), Ok(()));
+    }
+    assert_eq!(validate_bet(&inside(RouletteBetType::Line, &[31, 32, 33, 34, 35, 36])), Ok(()));
+}
+
+#[test]
+fn rejects_shapes_off_the_layout() {
+    let every_number: Vec<u8> = (0..=36).collect();
+    assert_eq!(
+        validate_bet(&inside(RouletteBetType::Split, &every_number)),
+        Err(RouletteBetError::InvalidShape(RouletteBetType::Split))
+    );
+    // 3 and 4 are consecutive but sit at opposite ends of two rows
+    for (bet_type, numbers) in [
+        (RouletteBetType::Split, vec![3, 4]),
+        (RouletteBetType::Split, vec![5, 5]),
+        (RouletteBetType::Street, vec![2, 3, 4]),
+        (RouletteBetType::Corner, vec![3, 4, 6, 7]),
+        (RouletteBetType::Line, vec![2, 3, 4, 5, 6, 7]),
+    ] {
+        assert_eq!(
+            validate_bet(&inside(bet_type, &numbers)),
+            Err(RouletteBetError::InvalidShape(bet_type))
+        );
+    }
+}
+
+#[test]
+fn rejects_bad_numbers_and_amounts() {
+    let straight = |number| RouletteBet {
+        bet_type: RouletteBetType::Number,
+        number,
+        numbers: None,
+        amount: 1,
+    };
+    assert_eq!(validate_bet(&straight(Some(36))), Ok(()));
+    assert_eq!(validate_bet(&straight(Some(37))), Err(RouletteBetError::NumberOutOfRange(37)));
+    assert_eq!(
+        validate_bet(&straight(None)),
+        Err(RouletteBetError::MissingNumbers(RouletteBetType::Number))
+    );
+
+    let mut red = straight(None);
+    red.bet_type = RouletteBetType::Red;
+    assert_eq!(validate_bets(&[red.clone()]), Ok(()));
+    red.amount = 0;
+    assert_eq!(validate_bets(&[red]), Err(RouletteBetError::ZeroAmount));
+    assert_eq!(validate_bets(&[]), Err(RouletteBetError::NoBets));
+}
+
+#[test]
+fn payouts_follow_the_odds() {
+    let straight = RouletteBet { bet_type: RouletteBetType::Number, number: Some(17), numbers: None, amount: 2 };
+    let red = RouletteBet { bet_type: RouletteBetType::Red, number: None, numbers: None, amount: 5 };
+    let bets = [straight, red];
+
+    assert_eq!(calculate_roulette_payout(&bets, 17), 72); // 17 is black
+    assert_eq!(calculate_roulette_payout(&bets, 1), 10);
+    assert_eq!(calculate_roulette_payout(&bets, 0), 0);
+    assert_eq!(roulette_max_liability(&bets), 72 - 7);
+    assert!(spin_roulette(3, RngVersion::CURRENT) <= MAX_NUMBER);
+}
@@ -0,0 +1,97 @@
+//! Shoe: composition, cut card and dealing from what is left.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use contracts::games::baccarat::run_baccarat_game;
+use contracts::games::create_decks;
+use contracts::games::shoe::{min_round_cards, Shoe};
+use contracts::rng::RngVersion;
+use contracts::BlackjackRules;
+
+#[test]
+fn full_shoe_matches_the_decks() {
+    for decks in [1, 6, 8] {
+        let shoe = Shoe::new(decks);
+        assert_eq!(shoe.cards(), create_decks(decks));
+        assert_eq!((shoe.cards_left(), shoe.dealt()), (decks as usize * 52, 0));
+    }
+}
+
+#[test]
+fn dealt_cards_leave_the_shoe() {
+    let mut shoe = Shoe::new(8);
+    let (_, player, banker, ..) = run_baccarat_game(&shoe, 3, RngVersion::CURRENT);
+    shoe.remove(&player).unwrap();
+    shoe.remove(&banker).unwrap();
+    assert_eq!(shoe.dealt(), player.len() + banker.len());
+
+    // The next coup is shuffled from the remaining cards only
+    let (_, next_player, next_banker, ..) = run_baccarat_game(&shoe, 4, RngVersion::CURRENT);
+    let shuffled = shoe.shuffled(4, RngVersion::CURRENT);
+    assert_eq!(shuffled.len(), shoe.cards_left());
+    assert!(next_player.iter().chain(&next_banker).all(|card| shuffled.contains(card)));
+}
+
+#[test]
+fn a_single_deck_runs_out_of_copies() {
+    let mut shoe = Shoe::new(1);
+    let ace = shoe.cards().into_iter().find(|card| card.id == "ace_of_spades").unwrap();
+    shoe.remove(&[ace.clone()]).unwrap();
+    assert!(!shoe.cards().contains(&ace));
+    // Dealing the same card twice is rejected and leaves the shoe as it was
+    let king = shoe.cards().into_iter().find(|card| card.id == "king_of_spades").unwrap();
+    assert!(shoe.remove(&[king, ace]).is_err());
+    assert_eq!(shoe.cards_left(), 51);
+}
+
+#[test]
+fn a_round_deals_on_from_the_discards() {
+    let mut shoe = Shoe::new(1);
+    let cards = shoe.cards();
+    shoe.remove(&cards[..47]).unwrap();
+    let mut deck = shoe.deal(7, RngVersion::CURRENT);
+    let dealt: Vec<_> = (0..30).map(|_| deck.draw()).collect();
+    assert!(deck.reshuffled());
+    // The five cards left come first, then the discards, and none twice
+    assert!(dealt[..5].iter().all(|card| shoe.cards().contains(card)));
+    let mut ids: Vec<&str> = dealt.iter().map(|card| card.id.as_str()).collect();
+    ids.sort_unstable();
+    ids.dedup();
+    assert_eq!(ids.len(), 30);
+}
+
+#[test]
+fn cut_card_triggers_a_reshuffle() {
+    let mut shoe = Shoe::new(8);
+    let cards = shoe.cards();
+    // 75% penetration of 416 cards is 312
+    shoe.remove(&cards[..311]).unwrap();
+    assert!(!shoe.past_cut_card(75, 1));
+    shoe.remove(&cards[311..312]).unwrap();
+    assert!(shoe.past_cut_card(75, 1));
+
+    shoe.reshuffle();
+    assert_eq!((shoe.shuffles, shoe.dealt()), (1, 0));
+    assert_eq!(shoe.cards(), create_decks(8));
+}
+
+#[test]
+fn small_shoes_keep_enough_cards_for_a_round() {
+    let mut shoe = Shoe::new(1);
+    let cards = shoe.cards();
+    let min_cards = min_round_cards(1);
+    shoe.remove(&cards[..52 - min_cards]).unwrap();
+    assert!(!shoe.past_cut_card(100, 1));
+    // A round at more seats needs a longer shoe
+    assert!(shoe.past_cut_card(100, 3));
+    shoe.remove(&cards[52 - min_cards..53 - min_cards]).unwrap();
+    assert!(shoe.past_cut_card(100, 1));
+}
+
+#[test]
+fn a_full_shoe_must_cover_every_seat() {
+    let rules = |decks, max_seats| BlackjackRules { decks, max_seats, ..Default::default() };
+    assert!(rules(1, 3).is_valid());
+    assert!(!rules(1, 5).is_valid());
+    assert!(rules(2, 5).is_valid());
+}
@@ -70,7 +70,7 @@ DEPLOY_OUTPUT=$(linera publish-and-create \
   contracts/target/wasm32-unknown-unknown/release/contracts_contract.wasm \
   contracts/target/wasm32-unknown-unknown/release/contracts_service.wasm \
   --json-parameters "{\"bank_chain_id\":\"$CHAIN_ID\"}" \
-  --json-argument '{"starting_balance": 100, "random_seed": 12345}')
+  --json-argument '{"starting_balance": 100}')
 
 echo "$DEPLOY_OUTPUT"
 
@@ -0,0 +1,33 @@
+import { lineraAdapter } from "@/lib/linera-adapter";
+
+const POLL_INTERVAL_MS = 500;
+const MAX_POLLS = 60;
+
+async function reservedCommitment(): Promise<string | null> {
+  const data = await lineraAdapter.queryApplication<{ seedCommitment: string | null }>(
+    `query { seedCommitment }`
+  );
+  return data.seedCommitment;
+}
+
+/**
+ * Make sure the Bank has reserved the seed commitment of the next game, then
+ * pick a fresh client seed. The seed is only drawn once the commitment is in,
+ * so the server seed is fixed before it. Returns the `clientSeed` and `nonce`
+ * arguments for a play mutation.
+ */
+export async function reserveClientSeed(): Promise<string> {
+  if (!(await reservedCommitment())) {
+    await lineraAdapter.mutate(`mutation { requestSeedCommitment }`);
+    let polls = 0;
+    while (!(await reservedCommitment())) {
+      if (++polls >= MAX_POLLS) {
+        throw new Error("The Bank did not reserve a seed commitment");
+      }
+      await new Promise((resolve) => setTimeout(resolve, POLL_INTERVAL_MS));
+    }
+  }
+  const bytes = crypto.getRandomValues(new Uint8Array(16));
+  const clientSeed = Array.from(bytes, (byte) => byte.toString(16).padStart(2, "0")).join("");
+  return `clientSeed: "${clientSeed}", nonce: 0`;
+}
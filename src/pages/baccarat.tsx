@@ -1,6 +1,7 @@
 
 import { useEffect, useState, useCallback, useRef } from "react";
 import { lineraAdapter } from "@/lib/linera-adapter";
+import { reserveClientSeed } from "@/lib/seed-commitment";
 import { CONTRACTS_APP_ID } from "@/constants";
 import { useGame } from "@/context/GameContext";
 import { playBaccaratRound } from "../lib/baccarat-utils";
@@ -333,7 +334,7 @@ export default function BaccaratPage() {
         setLastSeenGameId(currentLatestId);
 
         try {
-            const mutation = `mutation { playBaccarat(amount: ${betAmount}, betType: ${betType}) }`;
+            const mutation = `mutation { playBaccarat(amount: ${betAmount}, betType: ${betType}, ${await reserveClientSeed()}) }`;
             await lineraAdapter.mutate(mutation);
         } catch (e: any) {
             console.error("Bet failed:", e);
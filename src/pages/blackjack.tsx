@@ -11,6 +11,7 @@ import {
   type BlackjackCard,
 } from "../lib/blackjack-utils";
 import { lineraAdapter } from "@/lib/linera-adapter";
+import { reserveClientSeed } from "@/lib/seed-commitment";
 import Header from "../components/Header";
 
 import { CONTRACTS_APP_ID } from "../constants";
@@ -24,13 +25,15 @@ type Result =
   | "DealerWin"
   | "PlayerBust"
   | "DealerBust"
-  | "Push";
+  | "Push"
+  | "Refunded"
+  | "Abandoned";
 
 type ChainCard = { suit: string; value: string; id: string };
 
 interface CurrentGame {
   gameId: number;
-  seed: number;
+  commitment: string;
   bet: number;
   phase: string;
   playerHands: ChainCard[][];  // Now a 2D array for split hands
@@ -98,6 +101,9 @@ function normalizePhase(phase: string): Phase {
     PlayerTurn: "PlayerTurn",
     DEALER_TURN: "DealerTurn",
     DealerTurn: "DealerTurn",
+    // The operator is revealing the cards of the last action
+    AWAITING_CARDS: "DealerTurn",
+    AwaitingCards: "DealerTurn",
     ROUND_COMPLETE: "RoundComplete",
     RoundComplete: "RoundComplete",
   };
@@ -113,8 +119,8 @@ export default function Blackjack() {
 
   // State from chain
   const [balance, setBalance] = useState<number>(0);
-  const [allowedBets, setAllowedBets] = useState<number[]>([1, 2, 3, 4, 5]);
-  const [bet, setBet] = useState<number>(1);
+  const [allowedBets, setAllowedBets] = useState<number[]>([10, 20, 30, 40, 50]);
+  const [bet, setBet] = useState<number>(10);
   const [lastBet, setLastBet] = useState<number>(1);
   const [phase, setPhase] = useState<Phase>("WaitingForGame");
   const [lastResult, setLastResult] = useState<Result>(null);
@@ -179,7 +185,7 @@ export default function Blackjack() {
                     playerBalance
                     currentGame {
                         gameId
-                        seed
+                        commitment
                         bet
                         phase
                         playerHands { suit value id }
@@ -208,7 +214,7 @@ export default function Blackjack() {
       console.log("State refreshed:", data);
 
       setBalance(data.playerBalance || 0);
-      setAllowedBets(data.allowedBets || [1, 2, 3, 4, 5]);
+      setAllowedBets(data.allowedBets || [10, 20, 30, 40, 50]);
 
       const allHistory = data.gameHistory || [];
       console.log("All game history:", allHistory.map((g: any) => ({ gameId: g.gameId, gameType: g.gameType })));
@@ -259,9 +265,11 @@ export default function Blackjack() {
         const latestGame = newHistory.length > 0 ? newHistory[newHistory.length - 1] : null;
         const isNewResult = latestGame && latestGame.gameId !== lastShownGameId;
 
-        // Only show history results when waitingForResult (after stand/double/bust)
+        // Only show history results for the round we were waiting on or playing
+        // (a natural settles straight from the deal, a bust with the hit's card)
         // AND only if the game was started in this session (not old games after refresh)
-        if (gameStartedThisSession && waitingForResult && latestGame && isNewResult) {
+        const roundSettled = waitingForResult || waitingForSeed || currentGameId !== null;
+        if (gameStartedThisSession && roundSettled && latestGame && isNewResult) {
           setLastResult(latestGame.result);
           setLastPayout(latestGame.payout);
           setLastBet(latestGame.bet);
@@ -277,6 +285,8 @@ export default function Blackjack() {
           setDealerHand(normalizeCards(latestGame.dealerHand));
           setPhase("RoundComplete");
           setWaitingForResult(false);
+          setWaitingForSeed(false);
+          setCurrentGameId(null);
           setLastShownGameId(latestGame.gameId);
           // Show popup after 3 second delay so player can see the cards
           setTimeout(() => {
@@ -297,7 +307,7 @@ export default function Blackjack() {
     } catch (err) {
       console.error("Failed to refresh game state:", err);
     }
-  }, [waitingForSeed, waitingForResult, lastShownGameId, phase, gameStartedThisSession]);
+  }, [waitingForSeed, waitingForResult, lastShownGameId, phase, gameStartedThisSession, currentGameId]);
 
   useEffect(() => {
     const handleConnectionChange = () => {
@@ -339,7 +349,7 @@ export default function Blackjack() {
         mutation = `mutation { requestChips }`;
       } else if (action === "playBlackjack") {
         const betAmount = (args as any).bet;
-        mutation = `mutation { playBlackjack(bet: ${betAmount}) }`;
+        mutation = `mutation { playBlackjack(bet: ${betAmount}, ${await reserveClientSeed()}) }`;
         setWaitingForSeed(true);
         setGameStartedThisSession(true);
         setLastResult(null);
@@ -4,6 +4,7 @@ import Wheel from "../components/roulette/Wheel";
 import { GameStages } from "../components/roulette/Global";
 import "../components/roulette/roulette.css";
 import { lineraAdapter } from "@/lib/linera-adapter";
+import { reserveClientSeed } from "@/lib/seed-commitment";
 import { CONTRACTS_APP_ID } from "@/constants";
 import { useGame } from "@/context/GameContext";
 import { RouletteBoardBlueprint, BET_REGISTRY, calculatePayout } from "../components/roulette-blueprint";
@@ -313,7 +314,7 @@ const RoulettePage = () => {
       // Save current bets for repeat functionality
       setLastBets(new Map(placedBets));
 
-      const mutation = `mutation { playRoulette(bets: [${betsString}]) }`;
+      const mutation = `mutation { playRoulette(bets: [${betsString}], ${await reserveClientSeed()}) }`;
       await lineraAdapter.mutate(mutation);
 
       // LOCK the balance display - the bet has been deducted on-chain now,